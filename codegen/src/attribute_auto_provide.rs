@@ -1,25 +1,114 @@
-use crate::{attribute_provide, utils::import_crate};
-use proc_macro2::TokenStream;
+use crate::attribute_provide;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::ItemImpl;
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    Ident, Item, Token, Type,
+};
 
-pub fn transform(attribute: &TokenStream, input: ItemImpl) -> TokenStream {
-    let base = attribute_provide::transform(attribute, input);
-    assert!(
-        base.generics.params.is_empty(),
-        "Only concrete types can be registered. Use auto_register! instead"
-    );
-
-    let crate_path = import_crate();
+pub fn transform(attribute: &TokenStream, input: Item) -> syn::Result<TokenStream> {
+    let params = AutoProvideParams::parse(attribute)?;
+    let base = match input {
+        Item::Impl(input) => {
+            if let Some((as_token, _services)) = params.services {
+                return Err(syn::Error::new_spanned(
+                    as_token,
+                    "`as` is only supported on struct and enum definitions",
+                ));
+            }
+            attribute_provide::transform_impl(input)?
+        }
+        Item::Struct(_) | Item::Enum(_) => {
+            let Some((_as_token, services)) = params.services else {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "expected services to provide, e.g. #[auto_provide(as = [dyn Trait])]",
+                ));
+            };
+            attribute_provide::transform_definition(services, input)?
+        }
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "attribute must be applied to a trait impl, or a struct or enum definition",
+            ))
+        }
+    };
+    if !base.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &base.generics,
+            "Only concrete types can be registered. Use auto_register! instead",
+        ));
+    }
 
+    let crate_path = base.crate_path;
     let provider_type = base.provider_type;
-    let service_type = base.service_type;
+    let service_types = base.service_types;
 
-    let register = quote! {
-        #crate_path::auto_register!(#provider_type, #service_type);
+    let register = if params.multi {
+        quote! {
+            #crate_path::auto_register_many!(#provider_type, #(#service_types),*);
+        }
+    } else {
+        quote! {
+            #crate_path::auto_register!(#provider_type, #(#service_types),*);
+        }
     };
 
-    TokenStream::from_iter([base.output, register])
+    Ok(TokenStream::from_iter([base.output, register]))
+}
+
+#[derive(Default)]
+struct AutoProvideParams {
+    multi: bool,
+    services: Option<(Token![as], Vec<Type>)>,
+}
+
+impl AutoProvideParams {
+    fn parse(attribute: &TokenStream) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        let params = Punctuated::<AutoProvideParam, Token![,]>::parse_terminated
+            .parse2(attribute.clone())?;
+        for param in params {
+            match param {
+                AutoProvideParam::Multi => parsed.multi = true,
+                AutoProvideParam::As(as_token, services) => {
+                    parsed.services = Some((as_token, services));
+                }
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+// `multi`, or `as = [dyn A, dyn B]`
+enum AutoProvideParam {
+    Multi,
+    As(Token![as], Vec<Type>),
+}
+
+impl Parse for AutoProvideParam {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![as]) {
+            let as_token = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let content;
+            bracketed!(content in input);
+            let services = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+            return Ok(Self::As(as_token, services.into_iter().collect()));
+        }
+        let ident: Ident = input.parse()?;
+        if ident == "multi" {
+            Ok(Self::Multi)
+        } else {
+            Err(syn::Error::new_spanned(
+                ident,
+                "attribute does not accept parameters other than `multi` and `as`",
+            ))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -33,9 +122,9 @@ mod test {
         let input = quote! {
             impl some::Trait for some::Struct {}
         };
-        let input: ItemImpl = parse2(input).unwrap();
+        let input: Item = parse2(input).unwrap();
         let attribute = TokenStream::new();
-        let actual = transform(&attribute, input).to_string();
+        let actual = transform(&attribute, input).unwrap().to_string();
         let expected = quote! {
             impl some::Trait for some::Struct {}
             impl depcon::Provider<dyn some::Trait> for some::Struct {
@@ -51,13 +140,74 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "attribute does not accept parameters")]
+    fn test_auto_provide_multi() {
+        let input = quote! {
+            impl some::Trait for some::Struct {}
+        };
+        let input: Item = parse2(input).unwrap();
+        let attribute = quote! { multi };
+        let actual = transform(&attribute, input).unwrap().to_string();
+        let expected = quote! {
+            impl some::Trait for some::Struct {}
+            impl depcon::Provider<dyn some::Trait> for some::Struct {
+                fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<dyn some::Trait> {
+                    self
+                }
+            }
+            depcon::auto_register_many!(some::Struct, dyn some::Trait);
+        }
+        .to_string();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_auto_provide_as() {
+        let input = quote! {
+            struct Store;
+        };
+        let input: Item = parse2(input).unwrap();
+        let attribute = quote! { as = [dyn Reader, Arc<Store>] };
+        let actual = transform(&attribute, input).unwrap().to_string();
+        let expected = quote! {
+            struct Store;
+            impl depcon::Provider<dyn Reader> for Store {
+                fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<dyn Reader> {
+                    self
+                }
+            }
+            depcon::auto_register!(Store, dyn Reader, Store);
+        }
+        .to_string();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_reject_as_on_impl() {
+        let input = quote! {
+            impl Trait for Struct {}
+        };
+        let input: Item = parse2(input).unwrap();
+        let attribute = quote! { multi, as = [dyn Other] };
+        let error = transform(&attribute, input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`as` is only supported on struct and enum definitions"
+        );
+    }
+
+    #[test]
     fn test_reject_params() {
         let input = quote! {
             impl Trait for Struct {}
         };
-        let input: ItemImpl = parse2(input).unwrap();
+        let input: Item = parse2(input).unwrap();
         let attribute = quote! { bad };
-        transform(&attribute, input);
+        let error = transform(&attribute, input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "attribute does not accept parameters other than `multi` and `as`"
+        );
     }
 }
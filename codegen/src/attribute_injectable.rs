@@ -0,0 +1,260 @@
+use crate::{
+    derive_injectable::{field_dependency, resolve_field},
+    utils::{import_crate, remove_crate_attribute},
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    Attribute, Field, FnArg, ImplItem, ImplItemMethod, Item, ItemImpl, Pat, ReturnType, Signature,
+    Type, Visibility,
+};
+
+pub fn transform(attribute: &TokenStream, input: Item) -> syn::Result<TokenStream> {
+    if !attribute.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attribute,
+            "attribute does not accept parameters",
+        ));
+    }
+    let Item::Impl(mut input) = input else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "attribute must be applied to an impl block, and to the constructor inside it",
+        ));
+    };
+    if let Some((_, path, _)) = &input.trait_ {
+        return Err(syn::Error::new_spanned(
+            path,
+            "attribute must be applied to an inherent impl block",
+        ));
+    }
+
+    let crate_path = import_crate(&input.attrs)?;
+    remove_crate_attribute(&mut input.attrs);
+    let constructor = take_constructor(&mut input)?;
+    let fields = constructor_fields(&constructor)?;
+    let asyncness = constructor.sig.asyncness.is_some();
+
+    let mut args = Vec::new();
+    let mut dependencies = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        args.push(resolve_field(field, asyncness)?);
+        dependencies.extend(field_dependency(index, field, &crate_path)?);
+    }
+    let ident = &constructor.sig.ident;
+    let await_ = asyncness.then(|| quote!(.await));
+    let call = quote!(Self::#ident(#(#args),*) #await_);
+    let body = if is_fallible(&constructor.sig)? {
+        quote!(#call.map_err(#crate_path::Error::provider::<Self>))
+    } else {
+        quote!(Ok(#call))
+    };
+
+    let self_ty = &input.self_ty;
+    let (impl_generics, _ty_generics, where_clause) = input.generics.split_for_impl();
+    let implemented = if asyncness {
+        quote! {
+            /// Generated by #[injectable]
+            impl #impl_generics #crate_path::AsyncInjectable for #self_ty #where_clause {
+                fn inject_async(
+                    container: &#crate_path::Container,
+                ) -> #crate_path::BoxFuture<'_, Result<Self, #crate_path::Error>> {
+                    Box::pin(async move { #body })
+                }
+                fn dependencies() -> Vec<#crate_path::Dependency> {
+                    vec![#(#dependencies),*]
+                }
+            }
+        }
+    } else {
+        quote! {
+            /// Generated by #[injectable]
+            impl #impl_generics #crate_path::Injectable for #self_ty #where_clause {
+                fn inject(container: &#crate_path::Container) -> Result<Self, #crate_path::Error> {
+                    #body
+                }
+                fn dependencies() -> Vec<#crate_path::Dependency> {
+                    vec![#(#dependencies),*]
+                }
+            }
+        }
+    };
+
+    Ok(TokenStream::from_iter([
+        input.into_token_stream(),
+        implemented,
+    ]))
+}
+
+// Find the single method marked `#[injectable]`, and remove the marker along
+// with any `#[inject(...)]` parameter attributes. Returns the method as it
+// was written.
+fn take_constructor(input: &mut ItemImpl) -> syn::Result<ImplItemMethod> {
+    let self_ty = input.self_ty.clone();
+    let mut constructors = input.items.iter_mut().filter_map(|item| match item {
+        ImplItem::Method(method) if method.attrs.iter().any(is_marker) => Some(method),
+        _ => None,
+    });
+    let Some(constructor) = constructors.next() else {
+        return Err(syn::Error::new_spanned(
+            self_ty,
+            "impl block must contain a constructor marked #[injectable]",
+        ));
+    };
+    if let Some(other) = constructors.next() {
+        return Err(syn::Error::new_spanned(
+            &other.sig.ident,
+            "impl block must contain only one constructor marked #[injectable]",
+        ));
+    }
+    let original = constructor.clone();
+    constructor.attrs.retain(|attr| !is_marker(attr));
+    for arg in &mut constructor.sig.inputs {
+        if let FnArg::Typed(arg) = arg {
+            arg.attrs.retain(|attr| !attr.path.is_ident("inject"));
+        }
+    }
+    Ok(original)
+}
+
+// Matches both `#[injectable]` and paths such as `#[depcon::injectable]`.
+fn is_marker(attr: &Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "injectable")
+}
+
+// Treat each constructor parameter like a field of a derived provider, so
+// it's resolved the same way.
+fn constructor_fields(constructor: &ImplItemMethod) -> syn::Result<Vec<Field>> {
+    constructor
+        .sig
+        .inputs
+        .iter()
+        .map(|arg| {
+            let FnArg::Typed(arg) = arg else {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "#[injectable] constructor must not take self",
+                ));
+            };
+            let ident = match &*arg.pat {
+                Pat::Ident(pat) => Some(pat.ident.clone()),
+                _ => None,
+            };
+            Ok(Field {
+                attrs: arg.attrs.clone(),
+                vis: Visibility::Inherited,
+                ident,
+                colon_token: Some(arg.colon_token),
+                ty: (*arg.ty).clone(),
+            })
+        })
+        .collect()
+}
+
+// Whether the constructor returns `Result<Self, E>` rather than `Self`.
+fn is_fallible(sig: &Signature) -> syn::Result<bool> {
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return Err(syn::Error::new_spanned(
+            &sig.ident,
+            "#[injectable] constructor must return Self or Result<Self, E>",
+        ));
+    };
+    Ok(match &**ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Result"),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use quote::quote;
+    use syn::parse2;
+
+    #[test]
+    fn test_injectable() {
+        let input = quote! {
+            impl Service {
+                #[injectable]
+                fn new(db: Arc<dyn Db>, #[inject(name = "primary")] cfg: Arc<Config>) -> Self {
+                    Self { db, cfg }
+                }
+            }
+        };
+        let input: Item = parse2(input).unwrap();
+        let actual = transform(&TokenStream::new(), input).unwrap().to_string();
+        let db = quote!(depcon::Dependency {
+            field: Some("db"),
+            service: depcon::TypeInfo::of::<<Arc<dyn Db> as depcon::DependencyField>::Service>(),
+            name: None,
+            kind: depcon::DependencyKind::Required,
+        });
+        let cfg = quote!(depcon::Dependency {
+            field: Some("cfg"),
+            service: depcon::TypeInfo::of::<<Arc<Config> as depcon::DependencyField>::Service>(),
+            name: Some("primary"),
+            kind: depcon::DependencyKind::Required,
+        });
+        let expected = quote! {
+            impl Service {
+                fn new(db: Arc<dyn Db>, cfg: Arc<Config>) -> Self {
+                    Self { db, cfg }
+                }
+            }
+            /// Generated by #[injectable]
+            impl depcon::Injectable for Service {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    Ok(Self::new(container.resolve()?, container.resolve_named("primary")?))
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#db, #cfg]
+                }
+            }
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_injectable_async_result() {
+        let input = quote! {
+            impl<T> Pool<T> {
+                #[depcon::injectable]
+                async fn connect() -> Result<Self, io::Error> {
+                    todo!()
+                }
+            }
+        };
+        let input: Item = parse2(input).unwrap();
+        let actual = transform(&TokenStream::new(), input).unwrap().to_string();
+        let expected = quote! {
+            impl<T> Pool<T> {
+                async fn connect() -> Result<Self, io::Error> {
+                    todo!()
+                }
+            }
+            /// Generated by #[injectable]
+            impl<T> depcon::AsyncInjectable for Pool<T> {
+                fn inject_async(
+                    container: &depcon::Container,
+                ) -> depcon::BoxFuture<'_, Result<Self, depcon::Error>> {
+                    Box::pin(async move {
+                        Self::connect().await.map_err(depcon::Error::provider::<Self>)
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![]
+                }
+            }
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+}
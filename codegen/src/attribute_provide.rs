@@ -1,61 +1,219 @@
-use crate::utils::import_crate;
-use proc_macro2::TokenStream;
+use crate::utils::{import_crate, remove_crate_attribute};
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{Generics, ItemImpl};
+use syn::{
+    parse::Parser, punctuated::Punctuated, GenericArgument, Generics, Ident, Item, ItemImpl,
+    Member, PathArguments, Token, Type,
+};
 
 pub struct ProvideAttribute {
+    pub crate_path: TokenStream,
     pub provider_type: TokenStream,
-    pub service_type: TokenStream,
+    pub service_types: Vec<TokenStream>,
     pub generics: Generics,
     pub output: TokenStream,
 }
 
-// TODO: Assert attribute stream is empty
-pub fn transform(attribute: &TokenStream, input: ItemImpl) -> ProvideAttribute {
-    assert!(attribute.is_empty(), "attribute does not accept parameters");
+pub fn transform(attribute: &TokenStream, input: Item) -> syn::Result<ProvideAttribute> {
+    match input {
+        Item::Impl(input) => {
+            if !attribute.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    attribute,
+                    "attribute does not accept parameters on trait impls",
+                ));
+            }
+            transform_impl(input)
+        }
+        Item::Struct(_) | Item::Enum(_) => {
+            let services =
+                Punctuated::<Type, Token![,]>::parse_terminated.parse2(attribute.clone())?;
+            if services.is_empty() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "expected services to provide, e.g. #[provide(dyn Trait)]",
+                ));
+            }
+            transform_definition(services.into_iter().collect(), input)
+        }
+        _ => Err(syn::Error::new(
+            Span::call_site(),
+            "attribute must be applied to a trait impl, or a struct or enum definition",
+        )),
+    }
+}
+
+// `impl Trait for Provider`, providing `dyn Trait`.
+pub fn transform_impl(mut input: ItemImpl) -> syn::Result<ProvideAttribute> {
+    let crate_path = import_crate(&input.attrs)?;
+    remove_crate_attribute(&mut input.attrs);
     let struct_type = input.self_ty.to_token_stream();
-    let trait_ = input
-        .trait_
-        .as_ref()
-        .expect("attribute must be applied to a trait impl")
-        .1
-        .to_token_stream();
+    let Some((_, trait_, _)) = &input.trait_ else {
+        let impl_token = input.impl_token;
+        let self_ty = &input.self_ty;
+        return Err(syn::Error::new_spanned(
+            quote!(#impl_token #self_ty),
+            "attribute must be applied to a trait impl",
+        ));
+    };
     let dyn_trait = quote! {dyn #trait_};
     let implemented = implement_provider(
+        &crate_path,
         &struct_type,
-        &quote! {#dyn_trait},
+        &dyn_trait,
         &input.generics,
         &quote! {self},
     );
-    let output = TokenStream::from_iter([input.into_token_stream(), implemented.output]);
-    ProvideAttribute {
-        output,
-        ..implemented
+    Ok(ProvideAttribute {
+        crate_path,
+        provider_type: struct_type,
+        service_types: vec![dyn_trait],
+        generics: input.generics.clone(),
+        output: TokenStream::from_iter([input.into_token_stream(), implemented]),
+    })
+}
+
+// A struct or enum definition providing each of `services`. Trait objects get
+// a generated `Provider` impl. The provider type itself, written as `Self`,
+// `Store` or `Arc<Store>`, already provides itself. Other concrete services,
+// e.g. `Arc<Config>`, are shared from the struct's one field of that type.
+pub fn transform_definition(services: Vec<Type>, mut input: Item) -> syn::Result<ProvideAttribute> {
+    let (attrs, ident, generics) = match &mut input {
+        Item::Struct(item) => (&mut item.attrs, &item.ident, &item.generics),
+        Item::Enum(item) => (&mut item.attrs, &item.ident, &item.generics),
+        _ => unreachable!("only called with structs and enums"),
+    };
+    let crate_path = import_crate(attrs)?;
+    remove_crate_attribute(attrs);
+    let ident = ident.clone();
+    let generics = generics.clone();
+    let provider_type = provider_type(&ident, &generics);
+
+    let mut service_types = Vec::new();
+    let mut implemented = Vec::new();
+    for service in services {
+        let written = service.clone();
+        let service = unwrap_arc(service);
+        if is_provider_type(&service, &ident) {
+            service_types.push(provider_type.clone());
+            continue;
+        }
+        let service_type = service.to_token_stream();
+        let body = if matches!(service, Type::TraitObject(_)) {
+            quote! {self}
+        } else {
+            let field = service_field(&input, &service, &written)?;
+            quote! {std::sync::Arc::clone(&self.#field)}
+        };
+        implemented.push(implement_provider(
+            &crate_path,
+            &provider_type,
+            &service_type,
+            &generics,
+            &body,
+        ));
+        service_types.push(service_type);
+    }
+
+    Ok(ProvideAttribute {
+        crate_path,
+        provider_type,
+        service_types,
+        generics,
+        output: quote!(#input #(#implemented)*),
+    })
+}
+
+// The only field of `input` with type `Arc<service>`.
+fn service_field(input: &Item, service: &Type, written: &Type) -> syn::Result<Member> {
+    let Item::Struct(item) = input else {
+        return Err(syn::Error::new_spanned(
+            written,
+            "enums can only provide trait objects and themselves, \
+             other services need a hand-written `Provider` impl",
+        ));
+    };
+    let service = service.to_token_stream().to_string();
+    let mut fields = item.fields.iter().enumerate().filter(|(_, field)| {
+        let written = field.ty.to_token_stream().to_string();
+        let ty = unwrap_arc(field.ty.clone()).to_token_stream().to_string();
+        ty != written && ty == service
+    });
+    match (fields.next(), fields.next()) {
+        (Some((index, field)), None) => Ok(field
+            .ident
+            .clone()
+            .map_or_else(|| Member::Unnamed(index.into()), Member::Named)),
+        (None, _) => Err(syn::Error::new_spanned(
+            written,
+            format!(
+                "no `Arc<{service}>` field to provide this service from, \
+                 add one or write the `Provider` impl by hand"
+            ),
+        )),
+        (Some(_), Some(_)) => Err(syn::Error::new_spanned(
+            written,
+            format!(
+                "several `Arc<{service}>` fields could provide this service, \
+                 use a hand-written `Provider` impl instead"
+            ),
+        )),
+    }
+}
+
+fn provider_type(ident: &Ident, generics: &Generics) -> TokenStream {
+    let (_impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
+    quote!(#ident #ty_generics)
+}
+
+// `Self`, or the provider's own name with any generic arguments.
+fn is_provider_type(ty: &Type, ident: &Ident) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.qself.is_none()
+        && path.path.leading_colon.is_none()
+        && path.path.segments.len() == 1
+        && (path.path.segments[0].ident == *ident || path.path.segments[0].ident == "Self")
+}
+
+// `Arc<T>` -> `T`, matching how dependency fields are written.
+fn unwrap_arc(ty: Type) -> Type {
+    let Type::Path(path) = &ty else {
+        return ty;
+    };
+    let Some(segment) = path
+        .path
+        .segments
+        .last()
+        .filter(|segment| segment.ident == "Arc")
+    else {
+        return ty;
+    };
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return ty;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => inner.clone(),
+        _ => ty,
     }
 }
 
 fn implement_provider(
+    crate_path: &TokenStream,
     target_type: &TokenStream,
     service_type: &TokenStream,
     generics: &Generics,
     body: &TokenStream,
-) -> ProvideAttribute {
-    let crate_path = import_crate();
+) -> TokenStream {
     let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
 
-    let output = quote! {
+    quote! {
         impl #impl_generics #crate_path::Provider<#service_type> for #target_type #where_clause {
             fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<#service_type> {
                 #body
             }
         }
-    };
-
-    ProvideAttribute {
-        provider_type: target_type.clone(),
-        service_type: service_type.clone(),
-        generics: generics.clone(),
-        output,
     }
 }
 
@@ -70,9 +228,9 @@ mod test {
         let input = quote! {
             impl some::Trait for some::Struct {}
         };
-        let input: ItemImpl = parse2(input).unwrap();
+        let input: Item = parse2(input).unwrap();
         let attribute = TokenStream::new();
-        let actual = transform(&attribute, input).output.to_string();
+        let actual = transform(&attribute, input).unwrap().output.to_string();
         let expected = quote! {
             impl some::Trait for some::Struct {}
             impl depcon::Provider<dyn some::Trait> for some::Struct {
@@ -95,9 +253,9 @@ mod test {
                 C: Send + Sync + 'static,
             {}
         };
-        let input: ItemImpl = parse2(input).unwrap();
+        let input: Item = parse2(input).unwrap();
         let attribute = TokenStream::new();
-        let actual = transform(&attribute, input).output.to_string();
+        let actual = transform(&attribute, input).unwrap().output.to_string();
         let expected = quote! {
             impl<A, C> Trait<A> for Struct<C>
             where
@@ -120,13 +278,85 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "attribute does not accept parameters")]
+    fn test_provide_definition() {
+        let input = quote! {
+            struct Store<T> {
+                inner: T,
+                config: Arc<Config>,
+            }
+        };
+        let input: Item = parse2(input).unwrap();
+        let attribute = quote! { dyn Reader, Arc<dyn Writer>, Arc<Self>, Arc<Config> };
+        let provided = transform(&attribute, input).unwrap();
+        let service_types: Vec<_> = provided
+            .service_types
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            service_types,
+            ["dyn Reader", "dyn Writer", "Store < T >", "Config"]
+        );
+        let expected = quote! {
+            struct Store<T> {
+                inner: T,
+                config: Arc<Config>,
+            }
+            impl<T> depcon::Provider<dyn Reader> for Store<T> {
+                fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<dyn Reader> {
+                    self
+                }
+            }
+            impl<T> depcon::Provider<dyn Writer> for Store<T> {
+                fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<dyn Writer> {
+                    self
+                }
+            }
+            impl<T> depcon::Provider<Config> for Store<T> {
+                fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<Config> {
+                    std::sync::Arc::clone(&self.config)
+                }
+            }
+        }
+        .to_string();
+
+        assert_eq!(provided.output.to_string(), expected);
+    }
+
+    #[test]
+    fn test_provide_definition_tuple_field() {
+        let input = quote! {
+            struct Store(Arc<Cache>, Arc<Config>);
+        };
+        let input: Item = parse2(input).unwrap();
+        let attribute = quote! { Arc<Config> };
+        let output = transform(&attribute, input).unwrap().output.to_string();
+        let expected = quote!(std::sync::Arc::clone(&self.1)).to_string();
+        assert!(output.contains(&expected), "{output}");
+
+        let input: Item = parse2(quote! {
+            struct Store(Arc<Config>, Arc<Config>);
+        })
+        .unwrap();
+        let error = transform(&attribute, input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "several `Arc<Config>` fields could provide this service, \
+             use a hand-written `Provider` impl instead"
+        );
+    }
+
+    #[test]
     fn test_reject_params() {
         let input = quote! {
             impl Trait for Struct {}
         };
-        let input: ItemImpl = parse2(input).unwrap();
+        let input: Item = parse2(input).unwrap();
         let attribute = quote! { bad };
-        transform(&attribute, input);
+        let error = transform(&attribute, input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "attribute does not accept parameters on trait impls"
+        );
     }
 }
@@ -1,60 +1,478 @@
 use crate::utils::import_crate;
-use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
-use syn::{spanned::Spanned, Data, DeriveInput, Fields};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Data, DataEnum, DeriveInput, Expr, Field, Fields, Ident, LitStr, Path, Token, Type,
+};
 
-pub fn transform(input: DeriveInput) -> TokenStream {
-    let crate_path = import_crate();
+pub fn transform(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = import_crate(&input.attrs)?;
 
-    let name = input.ident;
+    let name = &input.ident;
 
-    let generics = input.generics;
+    let generics = &input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let body = inject_body(&input.data);
+    let body = inject_body(input, &crate_path, false)?;
+    let dependencies = dependencies_body(input, &crate_path)?;
 
     let expanded = quote! {
         /// Generated by #[derive(Injectable)]
         impl #impl_generics #crate_path::Injectable for #name #ty_generics #where_clause {
-            fn inject(container: &mut #crate_path::Container) -> Result<Self, #crate_path::Error> {
-                Ok(Self #body)
+            fn inject(container: &#crate_path::Container) -> Result<Self, #crate_path::Error> {
+                #body
             }
+            fn dependencies() -> Vec<#crate_path::Dependency> {
+                vec![#dependencies]
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+pub fn transform_async(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let crate_path = import_crate(&input.attrs)?;
+
+    let name = &input.ident;
+
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = inject_body(input, &crate_path, true)?;
+    let dependencies = dependencies_body(input, &crate_path)?;
+
+    let expanded = quote! {
+        /// Generated by #[derive(AsyncInjectable)]
+        impl #impl_generics #crate_path::AsyncInjectable for #name #ty_generics #where_clause {
+            fn inject_async(
+                container: &#crate_path::Container,
+            ) -> #crate_path::BoxFuture<'_, Result<Self, #crate_path::Error>> {
+                Box::pin(async move { #body })
+            }
+            fn dependencies() -> Vec<#crate_path::Dependency> {
+                vec![#dependencies]
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+// Generate an expression to build the provider, either from its fields or by
+// passing them to a custom constructor.
+fn inject_body(
+    input: &DeriveInput,
+    crate_path: &TokenStream,
+    asyncness: bool,
+) -> syn::Result<TokenStream> {
+    let attributes = InjectAttributes::parse(&input.attrs)?;
+    if let Some(span) = attributes.field_param_span() {
+        return Err(syn::Error::new(
+            span,
+            "inject parameters other than `constructor` and `select` are only supported on fields",
+        ));
+    }
+    let data = match input.data {
+        Data::Struct(ref data) => data,
+        Data::Enum(ref data) => {
+            return inject_variant(&input.ident, attributes, data, crate_path, asyncness)
+        }
+        Data::Union(ref data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "Injectable can't be derived for unions",
+            ))
         }
     };
+    if let Some(select) = attributes.select {
+        return Err(syn::Error::new_spanned(
+            select,
+            "inject select is only supported on enums",
+        ));
+    }
+    let Some(constructor) = attributes.constructor else {
+        let fields = inject_fields(&data.fields, asyncness)?;
+        return Ok(quote!(Ok(Self #fields)));
+    };
+    // skipped fields are left for the constructor to fill in
+    let mut args = Vec::new();
+    for field in &data.fields {
+        let attributes = InjectAttributes::parse(&field.attrs)?;
+        if !matches!(attributes.source, Some(Source::Skip(_))) {
+            args.push(resolve_field(field, asyncness)?);
+        }
+    }
+    Ok(quote! {
+        #constructor(#(#args),*).map_err(#crate_path::Error::provider::<Self>)
+    })
+}
 
-    expanded
+// Generate statements that pick an enum variant by name with the selector
+// function, then inject that variant's fields.
+fn inject_variant(
+    ident: &Ident,
+    attributes: InjectAttributes,
+    data: &DataEnum,
+    crate_path: &TokenStream,
+    asyncness: bool,
+) -> syn::Result<TokenStream> {
+    if let Some(constructor) = attributes.constructor {
+        return Err(syn::Error::new_spanned(
+            constructor,
+            "inject constructor is not supported on enums",
+        ));
+    }
+    let Some(select) = attributes.select else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "enums require a variant selector, e.g. #[inject(select = path::to_fn)]",
+        ));
+    };
+    // the selector's parameter type decides which service is resolved
+    let config = if asyncness {
+        quote!(container.resolve_async().await?)
+    } else {
+        quote!(container.resolve()?)
+    };
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let name = ident.unraw().to_string();
+        let fields = inject_fields(&variant.fields, asyncness)?;
+        arms.push(quote! {
+            #name => Ok(Self::#ident #fields),
+        });
+    }
+    Ok(quote! {
+        let variant = #select(#config);
+        match ::core::convert::AsRef::<str>::as_ref(&variant) {
+            #(#arms)*
+            variant => {
+                let message = ::std::format!("no variant named {:?}", variant);
+                Err(#crate_path::Error::provider::<Self>(message))
+            }
+        }
+    })
 }
 
 // Generate an expression to inject each field from the DI container.
-fn inject_body(data: &Data) -> TokenStream {
-    match *data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Named(ref fields) => {
-                let recurse = fields.named.iter().map(|f| {
+fn inject_fields(fields: &Fields, asyncness: bool) -> syn::Result<TokenStream> {
+    match *fields {
+        Fields::Named(ref fields) => {
+            let recurse = fields
+                .named
+                .iter()
+                .map(|f| {
                     let name = &f.ident;
-                    quote_spanned! {f.span()=>
-                        #name: container.resolve()?
+                    let resolve = resolve_field(f, asyncness)?;
+                    Ok(quote_spanned! {f.span()=>
+                        #name: #resolve
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! {
+                { #(#recurse,)* }
+            })
+        }
+        Fields::Unnamed(ref fields) => {
+            let recurse = fields
+                .unnamed
+                .iter()
+                .map(|f| resolve_field(f, asyncness))
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! {
+                ( #(#recurse),* )
+            })
+        }
+        Fields::Unit => Ok(quote!()),
+    }
+}
+
+// Generate a list of static dependency descriptions, one per injected field,
+// plus the service passed to an enum's variant selector.
+fn dependencies_body(input: &DeriveInput, crate_path: &TokenStream) -> syn::Result<TokenStream> {
+    let mut recurse = Vec::new();
+    match input.data {
+        Data::Struct(ref data) => {
+            for (index, f) in data.fields.iter().enumerate() {
+                recurse.extend(field_dependency(index, f, crate_path)?);
+            }
+        }
+        // fields are labeled `Variant.field`, and only required if their
+        // variant is selected
+        Data::Enum(ref data) => {
+            let attributes = InjectAttributes::parse(&input.attrs)?;
+            if let Some(select) = attributes.select {
+                recurse.push(quote_spanned! {select.span()=>
+                    #crate_path::Dependency {
+                        field: None,
+                        service: #crate_path::selector_service(&#select),
+                        name: None,
+                        kind: #crate_path::DependencyKind::Required,
                     }
                 });
-                quote! {
-                    { #(#recurse,)* }
+            }
+            for variant in &data.variants {
+                for (index, f) in variant.fields.iter().enumerate() {
+                    let label = format!("{}.{}", variant.ident.unraw(), field_label(index, f));
+                    recurse.extend(describe_field(&label, f, crate_path, true)?);
                 }
             }
-            Fields::Unnamed(ref fields) => {
-                let recurse = fields.unnamed.iter().map(|f| {
-                    quote_spanned! {f.span()=>
-                        container.resolve()?
+        }
+        // rejected by inject_body
+        Data::Union(_) => {}
+    }
+    Ok(quote! {
+        #(#recurse),*
+    })
+}
+
+// Generate a `Dependency` describing a single field, unless the field isn't
+// resolved from the container.
+pub fn field_dependency(
+    index: usize,
+    field: &Field,
+    crate_path: &TokenStream,
+) -> syn::Result<Option<TokenStream>> {
+    describe_field(&field_label(index, field), field, crate_path, false)
+}
+
+// Name of a field in `Dependency::field`, or its index if it's unnamed.
+fn field_label(index: usize, field: &Field) -> String {
+    field
+        .ident
+        .as_ref()
+        .map_or_else(|| index.to_string(), |ident| ident.unraw().to_string())
+}
+
+fn describe_field(
+    label: &str,
+    field: &Field,
+    crate_path: &TokenStream,
+    conditional: bool,
+) -> syn::Result<Option<TokenStream>> {
+    let attributes = InjectAttributes::parse(&field.attrs)?;
+    if attributes.source.is_some() {
+        return Ok(None);
+    }
+    let name = attributes
+        .name
+        .map_or_else(|| quote!(None), |name| quote!(Some(#name)));
+    let kind = match FieldKind::of(&field.ty) {
+        FieldKind::Required if conditional => quote!(Optional),
+        FieldKind::Required => quote!(Required),
+        FieldKind::Optional => quote!(Optional),
+        FieldKind::Collection => quote!(Collection),
+        FieldKind::Lazy => quote!(Lazy),
+        FieldKind::Factory => quote!(Factory),
+    };
+    let ty = &field.ty;
+    Ok(Some(quote_spanned! {field.span()=>
+        #crate_path::Dependency {
+            field: Some(#label),
+            service: #crate_path::TypeInfo::of::<<#ty as #crate_path::DependencyField>::Service>(),
+            name: #name,
+            kind: #crate_path::DependencyKind::#kind,
+        }
+    }))
+}
+
+// Generate an expression to resolve a single field from the DI container.
+pub fn resolve_field(field: &Field, asyncness: bool) -> syn::Result<TokenStream> {
+    let attributes = InjectAttributes::parse(&field.attrs)?;
+    if let Some(path) = attributes.constructor.or(attributes.select) {
+        return Err(syn::Error::new_spanned(
+            path,
+            "inject constructor and select are not supported on fields",
+        ));
+    }
+    if let Some(source) = attributes.source {
+        if let Some(name) = attributes.name {
+            return Err(syn::Error::new(
+                name.span(),
+                "inject name can't be combined with `default`, `skip`, `with` or `value`",
+            ));
+        }
+        return Ok(match source {
+            Source::Default(_) | Source::Skip(_) => {
+                quote_spanned!(field.span()=> ::core::default::Default::default())
+            }
+            Source::With(path) => quote_spanned!(path.span()=> #path(container)?),
+            Source::Value(expr) => quote_spanned!(field.span()=> #expr),
+        });
+    }
+    let name = attributes.name;
+    let kind = FieldKind::of(&field.ty);
+    if let (FieldKind::Collection, Some(name)) = (&kind, &name) {
+        return Err(syn::Error::new(
+            name.span(),
+            "inject name is not supported on collection fields",
+        ));
+    }
+    let method = match kind {
+        FieldKind::Required => "resolve",
+        FieldKind::Optional => "try_resolve",
+        FieldKind::Collection => "resolve_all",
+        FieldKind::Lazy => "lazy",
+        FieldKind::Factory => "factory",
+    };
+    let method = if name.is_some() {
+        format!("{method}_named")
+    } else {
+        method.to_owned()
+    };
+    // lazy handles and factories are created without resolving anything
+    let deferred = matches!(kind, FieldKind::Lazy | FieldKind::Factory);
+    let (method, await_) = if asyncness && !deferred {
+        let await_ = quote_spanned!(field.span()=> .await);
+        (format_ident!("{}_async", method), Some(await_))
+    } else {
+        (format_ident!("{}", method), None)
+    };
+    let try_ = (!matches!(kind, FieldKind::Lazy)).then(|| quote_spanned!(field.span()=> ?));
+    Ok(quote_spanned! {field.span()=>
+        container.#method(#name) #await_ #try_
+    })
+}
+
+// How a field is filled from the container, based on the outermost type.
+enum FieldKind {
+    // `Arc<T>`, resolved with `Container::resolve`.
+    Required,
+    // `Option<Arc<T>>`, resolved with `Container::try_resolve`.
+    Optional,
+    // `Vec<Arc<T>>`, resolved with `Container::resolve_all`.
+    Collection,
+    // `Lazy<T>`, created with `Container::lazy`.
+    Lazy,
+    // `Factory<T>`, created with `Container::factory`.
+    Factory,
+}
+
+impl FieldKind {
+    fn of(ty: &Type) -> Self {
+        let ident = match ty {
+            Type::Path(path) if path.qself.is_none() => {
+                path.path.segments.last().map(|segment| &segment.ident)
+            }
+            _ => None,
+        };
+        match ident {
+            Some(ident) if ident == "Option" => Self::Optional,
+            Some(ident) if ident == "Vec" => Self::Collection,
+            Some(ident) if ident == "Lazy" => Self::Lazy,
+            Some(ident) if ident == "Factory" => Self::Factory,
+            _ => Self::Required,
+        }
+    }
+}
+
+// Options parsed from `#[inject(...)]` attributes.
+#[derive(Default)]
+struct InjectAttributes {
+    // `name = "..."` on a field
+    name: Option<LitStr>,
+    // `constructor = path` on a struct
+    constructor: Option<Path>,
+    // `select = path` on an enum
+    select: Option<Path>,
+    // where a field comes from, if not the container
+    source: Option<Source>,
+}
+
+// How a field is filled without resolving it from the container.
+enum Source {
+    // `default`, filled with `Default::default()`.
+    Default(Span),
+    // `skip`, filled with `Default::default()`, or by the constructor.
+    Skip(Span),
+    // `with = path`, filled by calling `path(container)?`.
+    With(Path),
+    // `value = expr`, filled with the expression.
+    Value(Expr),
+}
+
+impl Source {
+    fn span(&self) -> Span {
+        match self {
+            Self::Default(span) | Self::Skip(span) => *span,
+            Self::With(path) => path.span(),
+            Self::Value(expr) => expr.span(),
+        }
+    }
+}
+
+impl InjectAttributes {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("inject")) {
+            let params =
+                attr.parse_args_with(Punctuated::<InjectParam, Token![,]>::parse_terminated)?;
+            for param in params {
+                match param {
+                    InjectParam::Name(name) => parsed.name = Some(name),
+                    InjectParam::Constructor(path) => parsed.constructor = Some(path),
+                    InjectParam::Select(path) => parsed.select = Some(path),
+                    InjectParam::Source(source) if parsed.source.is_some() => {
+                        return Err(syn::Error::new(
+                            source.span(),
+                            "inject parameters `default`, `skip`, `with` and `value` are \
+                            mutually exclusive",
+                        ));
                     }
-                });
-                quote! {
-                    ( #(#recurse),* )
+                    InjectParam::Source(source) => parsed.source = Some(source),
                 }
             }
-            Fields::Unit => {
-                quote!()
-            }
-        },
-        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+        }
+        Ok(parsed)
+    }
+    // Span of the first parameter that's only valid on fields.
+    fn field_param_span(&self) -> Option<Span> {
+        self.name
+            .as_ref()
+            .map(LitStr::span)
+            .or_else(|| self.source.as_ref().map(Source::span))
+    }
+}
+
+// A single `key` or `key = value` parameter of an `#[inject(...)]` attribute.
+enum InjectParam {
+    Name(LitStr),
+    Constructor(Path),
+    Select(Path),
+    Source(Source),
+}
+
+impl Parse for InjectParam {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if key == "default" {
+            return Ok(Self::Source(Source::Default(key.span())));
+        } else if key == "skip" {
+            return Ok(Self::Source(Source::Skip(key.span())));
+        }
+        input.parse::<Token![=]>()?;
+        if key == "name" {
+            Ok(Self::Name(input.parse()?))
+        } else if key == "constructor" {
+            Ok(Self::Constructor(input.parse()?))
+        } else if key == "select" {
+            Ok(Self::Select(input.parse()?))
+        } else if key == "with" {
+            Ok(Self::Source(Source::With(input.parse()?)))
+        } else if key == "value" {
+            Ok(Self::Source(Source::Value(input.parse()?)))
+        } else {
+            Err(syn::Error::new(
+                key.span(),
+                "unknown inject attribute parameter",
+            ))
+        }
     }
 }
 
@@ -62,19 +480,35 @@ fn inject_body(data: &Data) -> TokenStream {
 mod test {
     use super::*;
 
+    // Expected dependency description for a field of type `ty`.
+    fn dependency(field: &str, ty: TokenStream, name: Option<&str>, kind: &str) -> TokenStream {
+        let ty: Type = syn::parse2(ty).unwrap();
+        let name = name.map_or_else(|| quote!(None), |name| quote!(Some(#name)));
+        let kind = format_ident!("{}", kind);
+        quote!(depcon::Dependency {
+            field: Some(#field),
+            service: depcon::TypeInfo::of::<<#ty as depcon::DependencyField>::Service>(),
+            name: #name,
+            kind: depcon::DependencyKind::#kind,
+        })
+    }
+
     #[test]
     fn test_unit_struct() {
         let def = quote!(
             struct Unit;
         );
         let input: DeriveInput = syn::parse2(def).unwrap();
-        let actual = transform(input).to_string();
+        let actual = transform(&input).unwrap().to_string();
         let expected = quote!(
             /// Generated by #[derive(Injectable)]
             impl depcon::Injectable for Unit {
-                fn inject(container: &mut depcon::Container) -> Result<Self, depcon::Error> {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
                     Ok(Self)
                 }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![]
+                }
             }
         )
         .to_string();
@@ -87,13 +521,18 @@ mod test {
             struct Tuple(Foo, Bar);
         );
         let input: DeriveInput = syn::parse2(def).unwrap();
-        let actual = transform(input).to_string();
+        let actual = transform(&input).unwrap().to_string();
+        let foo = dependency("0", quote!(Foo), None, "Required");
+        let bar = dependency("1", quote!(Bar), None, "Required");
         let expected = quote!(
             /// Generated by #[derive(Injectable)]
             impl depcon::Injectable for Tuple {
-                fn inject(container: &mut depcon::Container) -> Result<Self, depcon::Error> {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
                     Ok(Self(container.resolve()?, container.resolve()?))
                 }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#foo, #bar]
+                }
             }
         )
         .to_string();
@@ -109,16 +548,369 @@ mod test {
             }
         );
         let input: DeriveInput = syn::parse2(def).unwrap();
-        let actual = transform(input).to_string();
+        let actual = transform(&input).unwrap().to_string();
+        let foo = dependency("foo", quote!(Foo), None, "Required");
+        let bar = dependency("bar", quote!(Bar), None, "Required");
         let expected = quote!(
             /// Generated by #[derive(Injectable)]
             impl depcon::Injectable for Named {
-                fn inject(container: &mut depcon::Container) -> Result<Self, depcon::Error> {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
                     Ok(Self {
                         foo: container.resolve()?,
                         bar: container.resolve()?,
                     })
                 }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#foo, #bar]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_async_named_struct() {
+        let def = quote!(
+            struct Named {
+                foo: Foo,
+                bar: Bar,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform_async(&input).unwrap().to_string();
+        let foo = dependency("foo", quote!(Foo), None, "Required");
+        let bar = dependency("bar", quote!(Bar), None, "Required");
+        let expected = quote!(
+            /// Generated by #[derive(AsyncInjectable)]
+            impl depcon::AsyncInjectable for Named {
+                fn inject_async(
+                    container: &depcon::Container,
+                ) -> depcon::BoxFuture<'_, Result<Self, depcon::Error>> {
+                    Box::pin(async move {
+                        Ok(Self {
+                            foo: container.resolve_async().await?,
+                            bar: container.resolve_async().await?,
+                        })
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#foo, #bar]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_named_field() {
+        let def = quote!(
+            struct Named {
+                #[inject(name = "replica")]
+                db: Db,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform(&input).unwrap().to_string();
+        let db = dependency("db", quote!(Db), Some("replica"), "Required");
+        let expected = quote!(
+            /// Generated by #[derive(Injectable)]
+            impl depcon::Injectable for Named {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    Ok(Self {
+                        db: container.resolve_named("replica")?,
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#db]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_collection_field() {
+        let def = quote!(
+            struct Named {
+                checks: Vec<Arc<dyn HealthCheck>>,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform(&input).unwrap().to_string();
+        let checks = dependency(
+            "checks",
+            quote!(Vec<Arc<dyn HealthCheck>>),
+            None,
+            "Collection",
+        );
+        let expected = quote!(
+            /// Generated by #[derive(Injectable)]
+            impl depcon::Injectable for Named {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    Ok(Self {
+                        checks: container.resolve_all()?,
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#checks]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_optional_field() {
+        let def = quote!(
+            struct Named {
+                metrics: Option<Arc<dyn Metrics>>,
+                #[inject(name = "audit")]
+                audit: Option<Arc<dyn Log>>,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform(&input).unwrap().to_string();
+        let metrics = dependency(
+            "metrics",
+            quote!(Option<Arc<dyn Metrics>>),
+            None,
+            "Optional",
+        );
+        let audit = dependency(
+            "audit",
+            quote!(Option<Arc<dyn Log>>),
+            Some("audit"),
+            "Optional",
+        );
+        let expected = quote!(
+            /// Generated by #[derive(Injectable)]
+            impl depcon::Injectable for Named {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    Ok(Self {
+                        metrics: container.try_resolve()?,
+                        audit: container.try_resolve_named("audit")?,
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#metrics, #audit]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_lazy_field() {
+        let def = quote!(
+            struct Named {
+                reports: Lazy<dyn Reports>,
+                #[inject(name = "admin")]
+                admin: depcon::Lazy<Admin>,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform_async(&input).unwrap().to_string();
+        let reports = dependency("reports", quote!(Lazy<dyn Reports>), None, "Lazy");
+        let admin = dependency("admin", quote!(depcon::Lazy<Admin>), Some("admin"), "Lazy");
+        let expected = quote!(
+            /// Generated by #[derive(AsyncInjectable)]
+            impl depcon::AsyncInjectable for Named {
+                fn inject_async(
+                    container: &depcon::Container,
+                ) -> depcon::BoxFuture<'_, Result<Self, depcon::Error>> {
+                    Box::pin(async move {
+                        Ok(Self {
+                            reports: container.lazy(),
+                            admin: container.lazy_named("admin"),
+                        })
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#reports, #admin]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_factory_field() {
+        let def = quote!(
+            struct Named {
+                parsers: Factory<Parser>,
+                #[inject(name = "strict")]
+                strict: Factory<Parser>,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform(&input).unwrap().to_string();
+        let parsers = dependency("parsers", quote!(Factory<Parser>), None, "Factory");
+        let strict = dependency("strict", quote!(Factory<Parser>), Some("strict"), "Factory");
+        let expected = quote!(
+            /// Generated by #[derive(Injectable)]
+            impl depcon::Injectable for Named {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    Ok(Self {
+                        parsers: container.factory()?,
+                        strict: container.factory_named("strict")?,
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#parsers, #strict]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_constructor() {
+        let def = quote!(
+            #[inject(constructor = Server::bind)]
+            struct Server {
+                config: Arc<Config>,
+                metrics: Option<Arc<Metrics>>,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform(&input).unwrap().to_string();
+        let config = dependency("config", quote!(Arc<Config>), None, "Required");
+        let metrics = dependency("metrics", quote!(Option<Arc<Metrics>>), None, "Optional");
+        let expected = quote!(
+            /// Generated by #[derive(Injectable)]
+            impl depcon::Injectable for Server {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    Server::bind(container.resolve()?, container.try_resolve()?)
+                        .map_err(depcon::Error::provider::<Self>)
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#config, #metrics]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_async_constructor() {
+        let def = quote!(
+            #[inject(constructor = Self::load)]
+            struct Config(Arc<Env>);
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform_async(&input).unwrap().to_string();
+        let env = dependency("0", quote!(Arc<Env>), None, "Required");
+        let expected = quote!(
+            /// Generated by #[derive(AsyncInjectable)]
+            impl depcon::AsyncInjectable for Config {
+                fn inject_async(
+                    container: &depcon::Container,
+                ) -> depcon::BoxFuture<'_, Result<Self, depcon::Error>> {
+                    Box::pin(async move {
+                        Self::load(container.resolve_async().await?)
+                            .map_err(depcon::Error::provider::<Self>)
+                    })
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#env]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_field_sources() {
+        let def = quote!(
+            #[inject(constructor = Self::new)]
+            struct Client {
+                config: Arc<Config>,
+                #[inject(default)]
+                retries: u32,
+                #[inject(with = load_url)]
+                url: String,
+                #[inject(value = Duration::from_secs(5))]
+                timeout: Duration,
+                #[inject(skip)]
+                pool: Pool,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform(&input).unwrap().to_string();
+        let config = dependency("config", quote!(Arc<Config>), None, "Required");
+        let args = quote! {
+            container.resolve()?,
+            ::core::default::Default::default(),
+            load_url(container)?,
+            Duration::from_secs(5)
+        };
+        let expected = quote!(
+            /// Generated by #[derive(Injectable)]
+            impl depcon::Injectable for Client {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    Self::new(#args).map_err(depcon::Error::provider::<Self>)
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![#config]
+                }
+            }
+        )
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_enum() {
+        let def = quote!(
+            #[inject(select = Storage::backend)]
+            enum Storage {
+                Local(Arc<LocalFs>),
+                S3 { client: Arc<S3Client> },
+                Memory,
+            }
+        );
+        let input: DeriveInput = syn::parse2(def).unwrap();
+        let actual = transform(&input).unwrap().to_string();
+        let local = dependency("Local.0", quote!(Arc<LocalFs>), None, "Optional");
+        let client = dependency("S3.client", quote!(Arc<S3Client>), None, "Optional");
+        let expected = quote!(
+            /// Generated by #[derive(Injectable)]
+            impl depcon::Injectable for Storage {
+                fn inject(container: &depcon::Container) -> Result<Self, depcon::Error> {
+                    let variant = Storage::backend(container.resolve()?);
+                    match ::core::convert::AsRef::<str>::as_ref(&variant) {
+                        "Local" => Ok(Self::Local(container.resolve()?)),
+                        "S3" => Ok(Self::S3 {
+                            client: container.resolve()?,
+                        }),
+                        "Memory" => Ok(Self::Memory),
+                        variant => {
+                            let message = ::std::format!("no variant named {:?}", variant);
+                            Err(depcon::Error::provider::<Self>(message))
+                        }
+                    }
+                }
+                fn dependencies() -> Vec<depcon::Dependency> {
+                    vec![
+                        depcon::Dependency {
+                            field: None,
+                            service: depcon::selector_service(&Storage::backend),
+                            name: None,
+                            kind: depcon::DependencyKind::Required,
+                        },
+                        #local,
+                        #client
+                    ]
+                }
             }
         )
         .to_string();
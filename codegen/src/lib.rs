@@ -5,9 +5,10 @@
     clippy::pedantic,
     clippy::cargo
 )]
-#![allow(clippy::wildcard_imports)]
+#![allow(clippy::wildcard_imports, clippy::multiple_crate_versions)]
 #![doc = include_str!("../README.md")]
 mod attribute_auto_provide;
+mod attribute_injectable;
 mod attribute_provide;
 mod derive_injectable;
 mod utils;
@@ -17,19 +18,47 @@ use syn::parse_macro_input;
 
 /// Procedural macro for `#[derive(Injectable)]`
 #[cfg(not(tarpaulin_include))]
-#[proc_macro_derive(Injectable)]
+#[proc_macro_derive(Injectable, attributes(inject, depcon))]
 pub fn derive_injectable(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input);
-    derive_injectable::transform(input).into()
+    derive_injectable::transform(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Procedural macro for `#[derive(AsyncInjectable)]`
+#[cfg(not(tarpaulin_include))]
+#[proc_macro_derive(AsyncInjectable, attributes(inject, depcon))]
+pub fn derive_async_injectable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input);
+    derive_injectable::transform_async(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Procedural macro for `#[injectable]`
+#[cfg(not(tarpaulin_include))]
+#[proc_macro_attribute]
+pub fn injectable(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item);
+    attribute_injectable::transform(&attribute.into(), input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 /// Procedural macro for `#[provide]`
+///
+/// On a struct or enum definition, lists the services it provides, e.g.
+/// `#[provide(dyn Reader, dyn Writer, Arc<Config>)]`. Trait objects and `Self`
+/// are served by the provider itself. Other concrete services are shared from
+/// the struct's one `Arc<Config>` field, or else need a hand-written `Provider`
+/// impl.
 #[cfg(not(tarpaulin_include))]
 #[proc_macro_attribute]
 pub fn provide(attribute: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item);
     attribute_provide::transform(&attribute.into(), input)
-        .output
+        .map_or_else(syn::Error::into_compile_error, |provide| provide.output)
         .into()
 }
 
@@ -38,5 +67,7 @@ pub fn provide(attribute: TokenStream, item: TokenStream) -> TokenStream {
 #[proc_macro_attribute]
 pub fn auto_provide(attribute: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item);
-    attribute_auto_provide::transform(&attribute.into(), input).into()
+    attribute_auto_provide::transform(&attribute.into(), input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
@@ -1,18 +1,235 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use std::{env, fs, path::Path};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Ident, LitStr, Token,
+};
+use toml::{Table, Value};
 
-pub fn import_crate() -> TokenStream {
-    quote!(depcon)
+const CRATE_NAME: &str = "depcon";
 
-    // TODO: Use proc-macro-crate to look up depcon crate and support renaming.
-    //       Doc comments break due to: https://github.com/bkchr/proc-macro-crate/issues/14
+// Path to depcon in generated code. Uses `#[depcon(crate = "path")]` if
+// present, or else the name depcon is imported under in Cargo.toml. Within
+// depcon itself, `depcon` refers to the crate through `extern crate self`.
+pub fn import_crate(attrs: &[Attribute]) -> syn::Result<TokenStream> {
+    let mut crate_path = None;
+    for attr in attrs.iter().filter(|attr| is_crate_attribute(attr)) {
+        let CrateParam(path) = attr.parse_args()?;
+        crate_path = Some(path);
+    }
+    Ok(crate_path.unwrap_or_else(find_crate))
+}
+
+// Attribute macros must remove `#[depcon(...)]` themselves, since it's only a
+// registered helper attribute for derives. It's kept for a derive still to
+// expand below the attribute, which needs it too.
+pub fn remove_crate_attribute(attrs: &mut Vec<Attribute>) {
+    if !attrs.iter().any(is_injectable_derive) {
+        attrs.retain(|attr| !is_crate_attribute(attr));
+    }
+}
+
+fn is_injectable_derive(attr: &Attribute) -> bool {
+    attr.path.is_ident("derive")
+        && attr
+            .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+            .is_ok_and(|paths| {
+                paths.iter().any(|path| {
+                    path.segments.last().is_some_and(|segment| {
+                        segment.ident == "Injectable" || segment.ident == "AsyncInjectable"
+                    })
+                })
+            })
+}
+
+fn is_crate_attribute(attr: &Attribute) -> bool {
+    attr.path.is_ident(CRATE_NAME)
+}
+
+// `crate = "path"`
+struct CrateParam(TokenStream);
+
+impl Parse for CrateParam {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![crate]>()?;
+        input.parse::<Token![=]>()?;
+        let path: LitStr = input.parse()?;
+        let path: syn::Path = path.parse()?;
+        Ok(Self(quote!(#path)))
+    }
+}
+
+fn find_crate() -> TokenStream {
+    let name = env::var_os("CARGO_MANIFEST_DIR").and_then(|dir| {
+        let dir = Path::new(&dir);
+        let manifest = read_manifest(dir)?;
+        crate_name(&manifest, workspace_dependencies(dir).as_ref())
+    });
+    let ident = Ident::new(name.as_deref().unwrap_or(CRATE_NAME), Span::call_site());
+    quote!(#ident)
+}
+
+fn read_manifest(dir: &Path) -> Option<Table> {
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    manifest.parse().ok()
+}
+
+// `[workspace.dependencies]` of the closest manifest with a `[workspace]`,
+// which may be the package's own.
+fn workspace_dependencies(dir: &Path) -> Option<Table> {
+    let workspace = dir
+        .ancestors()
+        .filter_map(read_manifest)
+        .find_map(|mut manifest| manifest.remove("workspace"))?;
+    match workspace.get("dependencies")? {
+        Value::Table(dependencies) => Some(dependencies.clone()),
+        _ => None,
+    }
+}
+
+// Name depcon is imported under, looking up dependencies inherited with
+// `workspace = true` in the workspace's dependencies.
+fn crate_name(manifest: &Table, workspace: Option<&Table>) -> Option<String> {
+    let targets = manifest
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(Table::values)
+        .filter_map(Value::as_table);
+    std::iter::once(manifest)
+        .chain(targets)
+        .flat_map(|table| {
+            ["dependencies", "dev-dependencies", "build-dependencies"]
+                .into_iter()
+                .filter_map(|section| table.get(section).and_then(Value::as_table))
+        })
+        .flatten()
+        .find(|(key, dependency)| {
+            let inherited = dependency.get("workspace").and_then(Value::as_bool) == Some(true);
+            let dependency = match workspace {
+                Some(workspace) if inherited => workspace.get(key.as_str()),
+                _ => Some(*dependency),
+            };
+            let package = dependency
+                .and_then(|dependency| dependency.get("package"))
+                .and_then(Value::as_str);
+            package.unwrap_or(key) == CRATE_NAME
+        })
+        .map(|(key, _dependency)| key.replace('-', "_"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::{parse_quote, DeriveInput};
+
+    fn manifest(toml: &str) -> Table {
+        toml.parse().unwrap()
+    }
+
+    #[test]
+    fn test_import_crate_override() {
+        let input: DeriveInput = parse_quote! {
+            #[depcon(crate = "::facade::di")]
+            struct Service;
+        };
+        let actual = import_crate(&input.attrs).unwrap().to_string();
+        assert_eq!(actual, quote!(::facade::di).to_string());
+    }
+
+    #[test]
+    fn test_remove_crate_attribute() {
+        let mut input: DeriveInput = parse_quote! {
+            #[derive(Debug)]
+            #[depcon(crate = "facade")]
+            struct Service;
+        };
+        remove_crate_attribute(&mut input.attrs);
+        assert_eq!(input.attrs.len(), 1);
+
+        let mut input: DeriveInput = parse_quote! {
+            #[derive(Debug, depcon::Injectable)]
+            #[depcon(crate = "facade")]
+            struct Service;
+        };
+        remove_crate_attribute(&mut input.attrs);
+        assert_eq!(input.attrs.len(), 2);
+    }
+
+    #[test]
+    fn test_crate_name() {
+        let plain = manifest(
+            r#"
+            [package]
+            name = "app"
+            [dependencies]
+            depcon = "0.3"
+            "#,
+        );
+        assert_eq!(crate_name(&plain, None).as_deref(), Some("depcon"));
+
+        let renamed = manifest(
+            r#"
+            [dependencies]
+            serde = "1"
+            [target.'cfg(unix)'.dev-dependencies]
+            my-di = { package = "depcon", version = "0.3" }
+            "#,
+        );
+        assert_eq!(crate_name(&renamed, None).as_deref(), Some("my_di"));
+
+        let missing = manifest(
+            r#"
+            [dependencies]
+            depcon = { package = "not-depcon", version = "1" }
+            "#,
+        );
+        assert_eq!(crate_name(&missing, None), None);
+
+        // depcon itself falls back to `depcon` through `extern crate self`
+        let own = manifest(
+            r#"
+            [package]
+            name = "depcon"
+            [dev-dependencies]
+            trybuild = "1"
+            "#,
+        );
+        assert_eq!(crate_name(&own, None), None);
+    }
+
+    #[test]
+    fn test_crate_name_workspace() {
+        let workspace = manifest(
+            r#"
+            depcon = "0.3"
+            my-di = { package = "depcon", version = "0.3" }
+            serde = "1"
+            "#,
+        );
+        let inherited = manifest(
+            r"
+            [dependencies]
+            depcon.workspace = true
+            ",
+        );
+        assert_eq!(
+            crate_name(&inherited, Some(&workspace)).as_deref(),
+            Some("depcon")
+        );
 
-    // let found_crate = crate_name("depcon").expect("depcon is present in `Cargo.toml`");
-    // match found_crate {
-    //     FoundCrate::Itself => quote!(crate),
-    //     FoundCrate::Name(name) => {
-    //         let ident = Ident::new(&name, Span::call_site());
-    //         quote!( #ident )
-    //     }
-    // }
+        let renamed = manifest(
+            r#"
+            [dependencies]
+            serde = { workspace = true }
+            my-di = { workspace = true, features = ["sync"] }
+            "#,
+        );
+        assert_eq!(
+            crate_name(&renamed, Some(&workspace)).as_deref(),
+            Some("my_di")
+        );
+    }
 }
@@ -0,0 +1,10 @@
+use depcon::*;
+
+trait Interface {}
+trait Other {}
+struct Implementation;
+
+#[auto_provide(as = [dyn Other])]
+impl Interface for Implementation {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use depcon::*;
+
+#[auto_provide]
+struct Implementation;
+
+fn main() {}
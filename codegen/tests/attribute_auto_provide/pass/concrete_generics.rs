@@ -1,7 +1,7 @@
 use depcon::*;
 use std::sync::Arc;
 
-trait Interface<A> {}
+trait Interface<A>: Send + Sync {}
 
 #[derive(Injectable)]
 struct Implementation;
@@ -10,6 +10,6 @@ struct Implementation;
 impl Interface<()> for Implementation {}
 
 fn main() {
-    let mut container = Container::auto().unwrap();
+    let container = Container::auto().unwrap();
     let _arc: Arc<dyn Interface<()>> = container.resolve().unwrap();
 }
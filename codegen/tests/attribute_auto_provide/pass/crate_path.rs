@@ -0,0 +1,24 @@
+use ::depcon::*;
+use std::sync::Arc;
+
+// Shadows the depcon crate, so only the overridden path resolves.
+mod depcon {}
+
+mod facade {
+    pub use ::depcon;
+}
+
+trait Interface: Send + Sync {}
+
+#[derive(Injectable)]
+#[depcon(crate = "facade::depcon")]
+struct Implementation;
+
+#[auto_provide]
+#[depcon(crate = "facade::depcon")]
+impl Interface for Implementation {}
+
+fn main() {
+    let container = Container::auto().unwrap();
+    let _arc: Arc<dyn Interface> = container.resolve().unwrap();
+}
@@ -0,0 +1,29 @@
+use depcon::*;
+use std::sync::Arc;
+
+trait Plugin: Send + Sync {}
+
+#[derive(Injectable)]
+struct First;
+
+#[auto_provide(multi)]
+impl Plugin for First {}
+
+#[derive(Injectable)]
+struct Second;
+
+#[auto_provide(multi)]
+impl Plugin for Second {}
+
+#[derive(Injectable)]
+struct Host {
+    plugins: Vec<Arc<dyn Plugin>>,
+}
+
+auto_register!(Host);
+
+fn main() {
+    let container = Container::auto().unwrap();
+    let host: Arc<Host> = container.resolve().unwrap();
+    assert_eq!(host.plugins.len(), 2);
+}
@@ -0,0 +1,22 @@
+use depcon::*;
+use std::sync::Arc;
+
+trait Reader: Send + Sync {}
+struct Config;
+
+#[derive(Injectable)]
+#[auto_provide(as = [dyn Reader, Arc<Config>])]
+struct Store {
+    #[inject(value = Arc::new(Config))]
+    config: Arc<Config>,
+}
+
+impl Reader for Store {}
+
+fn main() {
+    let container = Container::auto().unwrap();
+    let store: Arc<Store> = container.resolve().unwrap();
+    let config: Arc<Config> = container.resolve().unwrap();
+    assert!(Arc::ptr_eq(&store.config, &config));
+    let _reader: Arc<dyn Reader> = container.resolve().unwrap();
+}
@@ -0,0 +1,19 @@
+use depcon::*;
+use std::sync::Arc;
+
+trait Reader: Send + Sync {}
+trait Writer: Send + Sync {}
+
+#[derive(Injectable)]
+#[auto_provide(as = [dyn Reader, dyn Writer, Arc<Store>])]
+struct Store;
+
+impl Reader for Store {}
+impl Writer for Store {}
+
+fn main() {
+    let container = Container::auto().unwrap();
+    let _reader: Arc<dyn Reader> = container.resolve().unwrap();
+    let _writer: Arc<dyn Writer> = container.resolve().unwrap();
+    let _store: Arc<Store> = container.resolve().unwrap();
+}
@@ -0,0 +1,12 @@
+use depcon::*;
+
+struct Service;
+
+#[injectable]
+impl Service {
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use depcon::*;
+
+struct Service;
+
+#[injectable]
+impl Service {
+    #[injectable]
+    fn new(&self) -> Self {
+        Self
+    }
+}
+
+fn main() {}
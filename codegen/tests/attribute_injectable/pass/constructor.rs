@@ -0,0 +1,31 @@
+use depcon::*;
+use std::sync::Arc;
+
+trait Database: Send + Sync {}
+
+#[derive(Injectable)]
+struct Postgres;
+
+#[provide]
+impl Database for Postgres {}
+
+struct Repository {
+    _db: Arc<dyn Database>,
+    queries: u32,
+}
+
+#[injectable]
+impl Repository {
+    #[injectable]
+    fn new(db: Arc<dyn Database>) -> Self {
+        Self { _db: db, queries: 0 }
+    }
+}
+
+fn main() {
+    let mut container = Container::empty();
+    container.register::<Postgres, dyn Database>().unwrap();
+    container.register::<Repository, Repository>().unwrap();
+    let repository: Arc<Repository> = container.resolve().unwrap();
+    assert_eq!(repository.queries, 0);
+}
@@ -0,0 +1,26 @@
+use ::depcon::*;
+use std::sync::Arc;
+
+// Shadows the depcon crate, so only the overridden path resolves.
+mod depcon {}
+
+mod facade {
+    pub use ::depcon;
+}
+
+struct Config;
+
+#[injectable]
+#[depcon(crate = "facade::depcon")]
+impl Config {
+    #[injectable]
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn main() {
+    let mut container = Container::empty();
+    container.register::<Config, Config>().unwrap();
+    let _config: Arc<Config> = container.resolve().unwrap();
+}
@@ -0,0 +1,21 @@
+use depcon::*;
+use std::{num::ParseIntError, sync::Arc};
+
+struct Settings;
+
+struct Port(u16);
+
+#[injectable]
+impl Port {
+    #[injectable]
+    fn parse(#[inject(name = "port")] _settings: Option<Arc<Settings>>) -> Result<Self, ParseIntError> {
+        "http".parse().map(Self)
+    }
+}
+
+fn main() {
+    let mut container = Container::empty();
+    container.register::<Port, Port>().unwrap();
+    let error = container.resolve::<Port>().map(|port| port.0).unwrap_err();
+    assert!(matches!(error, Error::Provider { .. }));
+}
@@ -0,0 +1,10 @@
+use depcon::*;
+
+trait Reader {}
+struct Config;
+
+#[provide(dyn Reader, Arc<Config>)]
+struct Store;
+impl Reader for Store {}
+
+fn main() {}
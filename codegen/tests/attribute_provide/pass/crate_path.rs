@@ -0,0 +1,21 @@
+use ::depcon::*;
+use std::sync::Arc;
+
+// Shadows the depcon crate, so only the overridden path resolves.
+mod depcon {}
+
+mod facade {
+    pub use ::depcon;
+}
+
+trait Interface {}
+struct Implementation;
+
+#[provide]
+#[depcon(crate = "facade::depcon")]
+impl Interface for Implementation {}
+
+fn main() {
+    let arc = Arc::new(Implementation);
+    let _: Arc<dyn Interface> = arc.provide();
+}
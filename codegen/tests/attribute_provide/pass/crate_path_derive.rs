@@ -0,0 +1,25 @@
+use ::depcon::*;
+use std::sync::Arc;
+
+// Shadows the depcon crate, so only the overridden path resolves.
+mod depcon {}
+
+mod facade {
+    pub use ::depcon;
+}
+
+trait Reader {}
+
+// `#[provide]` expands first, and must leave the path for the derive.
+#[provide(dyn Reader)]
+#[derive(Injectable)]
+#[depcon(crate = "facade::depcon")]
+struct Store;
+
+impl Reader for Store {}
+
+fn main() {
+    let mut container = Container::empty();
+    container.register::<Store, dyn Reader>().unwrap();
+    let _: Arc<dyn Reader> = container.resolve().unwrap();
+}
@@ -0,0 +1,23 @@
+use depcon::*;
+use std::sync::Arc;
+
+trait Reader {}
+trait Writer {}
+struct Config;
+
+#[provide(dyn Reader, Arc<dyn Writer>, Arc<Store>, Arc<Config>)]
+struct Store {
+    config: Arc<Config>,
+}
+
+impl Reader for Store {}
+impl Writer for Store {}
+
+fn main() {
+    let arc = Arc::new(Store {
+        config: Arc::new(Config),
+    });
+    let _: Arc<dyn Reader> = arc.clone().provide();
+    let _: Arc<dyn Writer> = arc.clone().provide();
+    let _: Arc<Config> = arc.provide();
+}
@@ -0,0 +1,7 @@
+use depcon::*;
+
+#[derive(Injectable)]
+#[depcon(path = "facade::depcon")]
+struct Client;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use depcon::*;
+
+#[derive(Injectable)]
+enum Storage {
+    Local,
+    S3,
+}
+
+fn main() {}
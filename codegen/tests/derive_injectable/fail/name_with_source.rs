@@ -0,0 +1,9 @@
+use depcon::*;
+
+#[derive(Injectable)]
+struct Client {
+    #[inject(name = "retries", default)]
+    retries: u32,
+}
+
+fn main() {}
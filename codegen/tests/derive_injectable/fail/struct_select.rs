@@ -0,0 +1,11 @@
+use depcon::*;
+
+fn backend() -> &'static str {
+    "Local"
+}
+
+#[derive(Injectable)]
+#[inject(select = backend)]
+struct Storage;
+
+fn main() {}
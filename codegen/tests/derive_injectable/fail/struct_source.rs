@@ -0,0 +1,9 @@
+use depcon::*;
+
+#[derive(Injectable)]
+#[inject(default)]
+struct Client {
+    retries: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use depcon::*;
+
+#[derive(Injectable)]
+struct Client {
+    #[inject(fallback = 3)]
+    retries: u32,
+}
+
+fn main() {}
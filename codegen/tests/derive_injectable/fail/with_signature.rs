@@ -0,0 +1,13 @@
+use depcon::*;
+
+fn retries() -> u32 {
+    3
+}
+
+#[derive(Injectable)]
+struct Client {
+    #[inject(with = retries)]
+    retries: u32,
+}
+
+fn main() {}
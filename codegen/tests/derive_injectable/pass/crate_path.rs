@@ -0,0 +1,19 @@
+use ::depcon::*;
+use std::sync::Arc;
+
+// Shadows the depcon crate, so only the overridden path resolves.
+mod depcon {}
+
+mod facade {
+    pub use ::depcon;
+}
+
+trait Database {}
+
+#[derive(Injectable)]
+#[depcon(crate = "facade::depcon")]
+struct Repository {
+    _db: Arc<dyn Database>,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use depcon::*;
+use std::sync::Arc;
+
+struct Config {
+    backend: &'static str,
+}
+
+#[derive(Injectable)]
+struct LocalFs;
+
+#[derive(Injectable)]
+struct S3Client;
+
+#[derive(Injectable)]
+#[inject(select = Storage::backend)]
+enum Storage {
+    Local(Arc<LocalFs>),
+    S3 { _client: Arc<S3Client> },
+}
+
+impl Storage {
+    fn backend(config: Arc<Config>) -> &'static str {
+        config.backend
+    }
+}
+
+fn main() {
+    let mut container = Container::empty();
+    container
+        .register_instance(Arc::new(Config { backend: "Local" }))
+        .unwrap();
+    container.register::<LocalFs, LocalFs>().unwrap();
+    container.register::<Storage, Storage>().unwrap();
+    let storage: Arc<Storage> = container.resolve().unwrap();
+    assert!(matches!(*storage, Storage::Local(_)));
+}
@@ -0,0 +1,34 @@
+use depcon::*;
+use std::{sync::Arc, time::Duration};
+
+#[derive(Injectable)]
+struct Config;
+
+fn load_url(_container: &Container) -> Result<String, Error> {
+    Ok("postgres://localhost".to_owned())
+}
+
+#[derive(Injectable)]
+struct Client {
+    _config: Arc<Config>,
+    #[inject(default)]
+    retries: u32,
+    #[inject(with = load_url)]
+    url: String,
+    #[inject(value = Duration::from_secs(5))]
+    timeout: Duration,
+    #[inject(skip)]
+    requests: Vec<String>,
+}
+
+fn main() {
+    let mut container = Container::empty();
+    container.register::<Config, Config>().unwrap();
+    container.register::<Client, Client>().unwrap();
+    let client: Arc<Client> = container.resolve().unwrap();
+    assert_eq!(client.retries, 0);
+    assert_eq!(client.url, "postgres://localhost");
+    assert_eq!(client.timeout, Duration::from_secs(5));
+    assert!(client.requests.is_empty());
+    assert_eq!(Client::dependencies().len(), 1);
+}
@@ -0,0 +1,13 @@
+use depcon::*;
+use std::sync::Arc;
+
+trait Database {}
+
+#[derive(Injectable)]
+struct Repository {
+    primary: Arc<dyn Database>,
+    #[inject(name = "replica")]
+    replica: Arc<dyn Database>,
+}
+
+fn main() {}
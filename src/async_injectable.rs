@@ -0,0 +1,35 @@
+use crate::*;
+use std::{future::Future, pin::Pin};
+
+/// Boxed future returned by [`AsyncInjectable::inject_async`].
+///
+/// With the `sync` feature enabled, the future is also `Send`.
+#[cfg(not(feature = "sync"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Boxed future returned by [`AsyncInjectable::inject_async`].
+///
+/// With the `sync` feature enabled, the future is also `Send`.
+#[cfg(feature = "sync")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Trait for injecting providers that need to `.await` while being built.
+/// Use [`macro@AsyncInjectable`] instead of implementing manually!
+pub trait AsyncInjectable: Sized + 'static {
+    /// Build an instance of `Self`, using [`Container::resolve_async`] to
+    /// resolve dependencies.
+    ///
+    /// # Errors
+    /// Implementations should forward errors from [`Container::resolve_async`]
+    /// if a dependency can't be resolved.
+    fn inject_async(container: &Container) -> BoxFuture<'_, Result<Self, Error>>;
+
+    /// List the dependencies [`AsyncInjectable::inject_async`] resolves,
+    /// without resolving them. Used by [`Container::validate`].
+    ///
+    /// Defaults to an empty list.
+    #[must_use]
+    fn dependencies() -> Vec<Dependency> {
+        Vec::new()
+    }
+}
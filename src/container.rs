@@ -1,32 +1,232 @@
 use crate::*;
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, MutexGuard, PoisonError, Weak},
+};
+#[cfg(feature = "async")]
+use tokio::sync::OnceCell;
+
+mod builds;
+mod dot;
+mod introspect;
+mod manifest;
+mod register_as;
+mod suggest;
+
+use builds::Builds;
+pub use manifest::{Manifest, ManifestEntry, Origin};
+pub use register_as::RegisterAs;
 
 /// Dependency injection container where the magic happens.
 ///
 /// This struct holds information about what provider types provide what
 /// services and how to initialize them.
-#[derive(Default, Debug)]
+///
+/// Services are resolved through `&self`. With the `sync` feature enabled,
+/// the container is `Send + Sync` and can be shared between threads, e.g.
+/// in an [`Arc`].
+#[derive(Debug)]
 pub struct Container {
-    provider_factories: HashMap<TypeInfo, ProviderFactory>,
-    providers: HashMap<TypeInfo, DynamicBox>, // provider type -> Box<Arc<Provider>>
-    services: HashMap<TypeInfo, DynamicBox>,  // service type -> Box<Arc<Service>>
-    provide_map: HashMap<TypeInfo, (TypeInfo, ServiceConverter)>, // service -> provider
-    init_stack: Vec<Resolution>,
+    registry: Arc<RegistryCell>, // shared with child scopes until they register
+    singletons: Arc<Mutex<Cache>>, // shared with child scopes
+    scoped: Arc<Mutex<Cache>>,
+    root_scoped: Arc<Mutex<Cache>>, // root container's scope, used to build singletons
+    builds: Arc<Builds>,            // shared with child scopes
+    inherits_registry: bool,        // scope that hasn't registered its own providers yet
+    init_stack: Vec<Resolution>,    // per resolution chain, never shared
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        let scoped = Arc::default();
+        Self {
+            registry: Arc::default(),
+            singletons: Arc::default(),
+            root_scoped: Arc::clone(&scoped),
+            scoped,
+            builds: Arc::default(),
+            inherits_registry: false,
+            init_stack: Vec::new(),
+        }
+    }
+}
+
+/// Weak handle to a [`Container`], created with [`Container::downgrade`].
+///
+/// The handle keeps neither the container's registrations nor its cached
+/// instances alive, so services can hold one without creating reference
+/// cycles. Upgrading it sees providers registered after it was created.
+/// [`Lazy`] and [`Factory`] handles injected into a [`Lifetime::Singleton`]
+/// hold one to the root container, so they outlive the scope that resolved
+/// the singleton.
+#[derive(Clone, Debug)]
+pub struct WeakContainer {
+    registry: Weak<RegistryCell>,
+    singletons: Weak<Mutex<Cache>>,
+    scoped: Weak<Mutex<Cache>>,
+    root_scoped: Weak<Mutex<Cache>>,
+    builds: Arc<Builds>,
+}
+
+impl WeakContainer {
+    /// Get the container back, or `None` if it has been dropped.
+    ///
+    /// The container counts as dropped once its scope and every container
+    /// sharing its singletons have been dropped.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Container> {
+        Some(Container {
+            registry: self.registry.upgrade()?,
+            singletons: self.singletons.upgrade()?,
+            scoped: self.scoped.upgrade()?,
+            root_scoped: self.root_scoped.upgrade()?,
+            builds: Arc::clone(&self.builds),
+            inherits_registry: false,
+            init_stack: Vec::new(),
+        })
+    }
+    /// Same as [`WeakContainer::upgrade`], but fails if the container has
+    /// been dropped, reporting `T` as the service that couldn't be resolved.
+    ///
+    /// # Errors
+    /// Returns [`Error::ContainerDropped`] if the container has been dropped.
+    pub fn upgrade_for<T>(&self) -> Result<Container, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.upgrade().ok_or_else(|| Error::ContainerDropped {
+            service: TypeInfo::of::<T>(),
+        })
+    }
 }
 
+#[cfg(not(feature = "sync"))]
 type DynamicBox = Box<dyn Any>;
+#[cfg(not(feature = "sync"))]
+type FactoryFn = dyn Fn(&Container) -> Result<DynamicBox, Error>;
+#[cfg(not(feature = "sync"))]
+type ConverterFn = dyn Fn(&DynamicBox) -> Result<DynamicBox, Error>;
+#[cfg(all(feature = "async", not(feature = "sync")))]
+type AsyncFactoryFn = dyn for<'a> Fn(&'a Container) -> BoxFuture<'a, Result<DynamicBox, Error>>;
+
+#[cfg(feature = "sync")]
+type DynamicBox = Box<dyn Any + Send + Sync>;
+#[cfg(feature = "sync")]
+type FactoryFn = dyn Fn(&Container) -> Result<DynamicBox, Error> + Send + Sync;
+#[cfg(feature = "sync")]
+type ConverterFn = dyn Fn(&DynamicBox) -> Result<DynamicBox, Error> + Send + Sync;
+#[cfg(all(feature = "async", feature = "sync"))]
+type AsyncFactoryFn =
+    dyn for<'a> Fn(&'a Container) -> BoxFuture<'a, Result<DynamicBox, Error>> + Send + Sync;
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+struct ProviderFactory {
+    build: BuildFn,
+    #[derivative(Debug = "ignore")]
+    dependencies: fn() -> Vec<Dependency>,
+}
 
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
-struct ProviderFactory(
-    #[derivative(Debug = "ignore")] Arc<dyn Fn(&mut Container) -> Result<DynamicBox, Error>>,
-);
+enum BuildFn {
+    Sync(#[derivative(Debug = "ignore")] Arc<FactoryFn>),
+    #[cfg(feature = "async")]
+    Async(#[derivative(Debug = "ignore")] Arc<AsyncFactoryFn>),
+}
 
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
-struct ServiceConverter(
-    #[derivative(Debug = "ignore")] Arc<dyn Fn(&DynamicBox) -> Result<DynamicBox, Error>>,
-);
+struct ServiceConverter(#[derivative(Debug = "ignore")] Arc<ConverterFn>);
+
+#[derive(Clone, Debug)]
+struct Registration {
+    provider: TypeInfo,
+    converter: ServiceConverter,
+    lifetime: Lifetime,
+    origin: Origin,
+}
+
+#[derive(Clone, Default, Debug)]
+struct Registry {
+    provider_factories: HashMap<TypeInfo, ProviderFactory>,
+    provide_map: HashMap<ServiceKey, Registration>, // service -> provider
+    multi_map: HashMap<TypeInfo, Vec<Registration>>, // service -> providers
+}
+
+// The latest registry, replaced whenever a provider is registered, so
+// resolution works from a snapshot without holding the lock.
+type RegistryCell = Mutex<Arc<Registry>>;
+
+/// Mutable access to a container's registry, cloning it if a resolution
+/// still holds a snapshot.
+struct RegistryMut<'a>(MutexGuard<'a, Arc<Registry>>);
+
+impl Deref for RegistryMut<'_> {
+    type Target = Registry;
+    fn deref(&self) -> &Registry {
+        &self.0
+    }
+}
+
+impl DerefMut for RegistryMut<'_> {
+    fn deref_mut(&mut self) -> &mut Registry {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl Registry {
+    fn mark_hook_registrations(&mut self) {
+        let registrations = self
+            .provide_map
+            .values_mut()
+            .chain(self.multi_map.values_mut().flatten());
+        for registration in registrations {
+            if registration.origin != Origin::Implicit {
+                registration.origin = Origin::DefaultProviderHook;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct ServiceKey {
+    service: TypeInfo,
+    name: Option<&'static str>,
+}
+
+impl ServiceKey {
+    fn of<T: ?Sized + 'static>(name: Option<&'static str>) -> Self {
+        Self {
+            service: TypeInfo::of::<T>(),
+            name,
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+struct Cache {
+    providers: HashMap<TypeInfo, DynamicBox>, // provider type -> Box<Arc<Provider>>
+    services: HashMap<ServiceKey, DynamicBox>, // service type -> Box<Arc<Service>>
+    #[cfg(feature = "async")]
+    async_providers: HashMap<TypeInfo, Arc<OnceCell<DynamicBox>>>, // provider type -> pending init
+}
+
+impl Cache {
+    fn provider(&self, provider_type: &TypeInfo) -> Option<&DynamicBox> {
+        #[cfg(feature = "async")]
+        if let Some(provider) = self
+            .async_providers
+            .get(provider_type)
+            .and_then(|cell| cell.get())
+        {
+            return Some(provider);
+        }
+        self.providers.get(provider_type)
+    }
+}
 
 impl Container {
     /// Create a container with all providers pre-registered from
@@ -40,6 +240,7 @@ impl Container {
         for hook in inventory::iter::<DefaultProviderHook>() {
             hook.call(&mut container)?;
         }
+        container.registry_mut().mark_hook_registrations();
         Ok(container)
     }
     /// Create an empty container. Useful for testing and manual registration.
@@ -47,168 +248,1110 @@ impl Container {
     pub fn empty() -> Self {
         Self::default()
     }
-    fn init_provider(&mut self, res: Resolution) -> Result<&DynamicBox, Error> {
-        let cycle = self.init_stack.contains(&res);
-        self.init_stack.push(res);
-
-        // do main work inside closure to avoid early returns leaving init stack
-        // in a weird state
-        let init = &mut || -> Result<DynamicBox, Error> {
-            if cycle {
-                return Err(Error::DependencyCycle {
-                    service: res.service,
-                    stack: self.init_stack.clone(),
-                });
-            }
-            let factory = self
-                .provider_factories
-                .get(&res.provider)
-                .ok_or_else(|| {
-                    let provider = res.provider;
-                    let service = res.service;
-                    Error::Internal {
-                        message: format!("No factory for provider {provider} (service: {service})"),
-                    }
-                })?
-                .clone();
-            let provider = (factory.0)(self)?;
-            Ok(provider)
-        };
-        let result = init();
-        self.init_stack.pop();
-        let provider = result?;
-
-        // slightly hacky workaround to insert a value into a HashMap
-        // and also return a reference to that same value:
-        // delete the value from the map, then use the entry API's
-        // `or_insert` method, since we know the entry is now empty
-        self.providers.remove(&res.provider);
-        let entry = self.providers.entry(res.provider);
-        Ok(entry.or_insert(provider))
-    }
-    fn init_service(&mut self, service_type: TypeInfo) -> Result<&DynamicBox, Error> {
-        let (provider_type, converter) =
-            self.provide_map
-                .get(&service_type)
-                .cloned()
-                .ok_or(Error::NoProvider {
-                    service: service_type,
-                })?;
-
-        let resolution = Resolution {
-            service: service_type,
-            provider: provider_type,
+    /// Create a child container for resolving [`Lifetime::Scoped`] services.
+    ///
+    /// The scope shares this container's registrations and singleton
+    /// instances, but keeps its own instances of scoped services. These are
+    /// released when the scope is dropped.
+    ///
+    /// Singletons are always built by the root container, so a singleton
+    /// that depends on a scoped service gets the root container's instance,
+    /// no matter which scope resolved it first.
+    ///
+    /// Registering providers on the scope doesn't affect this container.
+    /// Once it does, the scope stops sharing this container's singletons,
+    /// and builds its own instead.
+    #[must_use]
+    pub fn create_scope(&self) -> Self {
+        Self {
+            registry: Arc::clone(&self.registry),
+            singletons: Arc::clone(&self.singletons),
+            scoped: Arc::default(),
+            root_scoped: Arc::clone(&self.root_scoped),
+            builds: Arc::clone(&self.builds),
+            inherits_registry: true,
+            init_stack: Vec::new(),
+        }
+    }
+    /// Create a weak handle to this container, which doesn't keep its
+    /// instances alive.
+    #[must_use]
+    pub fn downgrade(&self) -> WeakContainer {
+        WeakContainer {
+            registry: Arc::downgrade(&self.registry),
+            singletons: Arc::downgrade(&self.singletons),
+            scoped: Arc::downgrade(&self.scoped),
+            root_scoped: Arc::downgrade(&self.root_scoped),
+            builds: Arc::clone(&self.builds),
+        }
+    }
+    /// Create a [`Lazy`] handle that resolves an instance of type `T` the
+    /// first time it's used.
+    ///
+    /// Lazy handles can be used to break dependency cycles, since `T` isn't
+    /// resolved until [`Lazy::get`] is called. Resolution starts a new chain,
+    /// so don't call [`Lazy::get`] while the dependent service is still being
+    /// constructed.
+    #[must_use]
+    pub fn lazy<T>(&self) -> Lazy<T>
+    where
+        T: ?Sized + 'static,
+    {
+        Lazy::new(self.downgrade(), None)
+    }
+    /// Same as [`Container::lazy`], but for a named registration.
+    #[must_use]
+    pub fn lazy_named<T>(&self, name: &'static str) -> Lazy<T>
+    where
+        T: ?Sized + 'static,
+    {
+        Lazy::new(self.downgrade(), Some(name))
+    }
+    /// Create a [`Factory`] that builds a new instance of type `T` every time
+    /// [`Factory::create`] is called.
+    ///
+    /// # Errors
+    /// This method fails if no provider has been registered for `T`.
+    pub fn factory<T>(&self) -> Result<Factory<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.factory_key(ServiceKey::of::<T>(None))
+    }
+    /// Same as [`Container::factory`], but for a named registration.
+    ///
+    /// # Errors
+    /// This method fails if no provider has been registered for `T` with
+    /// this name.
+    pub fn factory_named<T>(&self, name: &'static str) -> Result<Factory<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.factory_key(ServiceKey::of::<T>(Some(name)))
+    }
+    fn factory_key<T>(&self, key: ServiceKey) -> Result<Factory<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.registration(key)?;
+        Ok(Factory::new(self.downgrade(), key.name))
+    }
+    pub(crate) fn create_instance<T>(&self, name: Option<&'static str>) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        let key = ServiceKey::of::<T>(name);
+        let registration = self.registration(key)?;
+        let res = Resolution {
+            service: key.service,
+            name,
+            provider: registration.provider,
         };
+        // factory instances are never cached, so they belong to this scope
+        let provider = self.build_provider(res, Lifetime::Transient)?;
+        let service = (registration.converter.0)(&provider)?;
+        Self::downcast_service(key.service, &service)
+    }
+    fn cache(&self, lifetime: Lifetime) -> Option<MutexGuard<'_, Cache>> {
+        // caches are only mutated by single inserts, so they're always valid
+        self.cache_cell(lifetime)
+            .map(|cache| cache.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+    const fn cache_cell(&self, lifetime: Lifetime) -> Option<&Arc<Mutex<Cache>>> {
+        match lifetime {
+            Lifetime::Singleton => Some(&self.singletons),
+            Lifetime::Scoped => Some(&self.scoped),
+            Lifetime::Transient => None,
+        }
+    }
+    fn registry(&self) -> Arc<Registry> {
+        // the registry is replaced as a whole, so it's always valid
+        Arc::clone(&self.registry.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+    fn registration(&self, key: ServiceKey) -> Result<Registration, Error> {
+        self.registry()
+            .provide_map
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| self.no_provider(key, self.init_stack.clone()))
+    }
+    fn is_provided(&self, key: ServiceKey) -> bool {
+        self.registry().provide_map.contains_key(&key)
+    }
+    fn multi_registrations(&self, key: ServiceKey) -> Vec<Registration> {
+        self.registry()
+            .multi_map
+            .get(&key.service)
+            .cloned()
+            .unwrap_or_default()
+    }
+    fn resolution_context(
+        &self,
+        res: Resolution,
+        lifetime: Lifetime,
+    ) -> Result<(Self, ProviderFactory), Error> {
+        // each nested resolution gets its own copy of the init stack, so
+        // concurrent resolutions on other threads can't trigger false cycles
+        let mut init_stack = self.init_stack.clone();
+        let cycle = init_stack.contains(&res);
+        init_stack.push(res);
+        if cycle {
+            return Err(Error::DependencyCycle {
+                service: res.service,
+                stack: init_stack,
+            });
+        }
 
-        let provider = match self.providers.get(&provider_type) {
-            Some(ptr) => ptr,
-            None => self.init_provider(resolution)?,
+        let factory = self
+            .registry()
+            .provider_factories
+            .get(&res.provider)
+            .ok_or_else(|| {
+                let provider = res.provider;
+                let service = res.service;
+                Error::Internal {
+                    message: format!("No factory for provider {provider} (service: {service})"),
+                }
+            })?
+            .clone();
+        // singletons outlive any child scope, so they must not capture its instances
+        let scoped = match lifetime {
+            Lifetime::Singleton => &self.root_scoped,
+            Lifetime::Scoped | Lifetime::Transient => &self.scoped,
+        };
+        let context = Self {
+            registry: Arc::clone(&self.registry),
+            singletons: Arc::clone(&self.singletons),
+            scoped: Arc::clone(scoped),
+            root_scoped: Arc::clone(&self.root_scoped),
+            builds: Arc::clone(&self.builds),
+            inherits_registry: false,
+            init_stack,
+        };
+        Ok((context, factory))
+    }
+    // A provider that's already being built further up the chain would wait
+    // on its own initialization, so report the cycle before touching the cache.
+    fn check_pending(&self, res: Resolution) -> Result<(), Error> {
+        if self
+            .init_stack
+            .iter()
+            .any(|pending| pending.provider == res.provider)
+        {
+            return Err(self.dependency_cycle(res));
+        }
+        Ok(())
+    }
+    fn dependency_cycle(&self, res: Resolution) -> Error {
+        let mut stack = self.init_stack.clone();
+        stack.push(res);
+        Error::DependencyCycle {
+            service: res.service,
+            stack,
+        }
+    }
+    fn build_provider(&self, res: Resolution, lifetime: Lifetime) -> Result<DynamicBox, Error> {
+        let (context, factory) = self.resolution_context(res, lifetime)?;
+        match factory.build {
+            BuildFn::Sync(build) => build(&context),
+            #[cfg(feature = "async")]
+            BuildFn::Async(_) => Err(Error::AsyncProvider {
+                service: res.service,
+                provider: res.provider,
+            }),
+        }
+    }
+    fn init_service(
+        &self,
+        key: ServiceKey,
+        registration: &Registration,
+    ) -> Result<DynamicBox, Error> {
+        let converter = &registration.converter.0;
+        let res = Resolution {
+            service: key.service,
+            name: key.name,
+            provider: registration.provider,
+        };
+        self.check_pending(res)?;
+        let cached = || {
+            self.cache(registration.lifetime).and_then(|cache| {
+                cache
+                    .provider(&registration.provider)
+                    .map(|provider| converter(provider))
+            })
+        };
+        if let Some(service) = cached() {
+            return service;
+        }
+        let Some(cache) = self.cache_cell(registration.lifetime) else {
+            return converter(&self.build_provider(res, registration.lifetime)?);
         };
 
-        let service = (converter.0)(provider)?;
-
-        // slightly hacky workaround to insert a value into a HashMap
-        // and also return a reference to that same value:
-        // delete the value from the map, then use the entry API's
-        // `or_insert` method, since we know the entry is now empty
-        self.services.remove(&service_type);
-        let entry = self.services.entry(service_type);
-        Ok(entry.or_insert(service))
+        // only one thread builds the provider, the others wait and reuse it
+        let Some(_claim) = self.builds.claim(cache, registration.provider) else {
+            return Err(self.dependency_cycle(res));
+        };
+        if let Some(service) = cached() {
+            return service;
+        }
+        let provider = self.build_provider(res, registration.lifetime)?;
+        let service = converter(&provider);
+        if let Some(mut cache) = self.cache(registration.lifetime) {
+            cache.providers.insert(registration.provider, provider);
+        }
+        service
+    }
+    fn downcast_service<T>(
+        service_type: TypeInfo,
+        service_ptr: &DynamicBox,
+    ) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        let service_ptr = service_ptr.downcast_ref::<Arc<T>>().ok_or_else(|| {
+            let box_type = TypeInfo::of::<DynamicBox>();
+            Error::Internal {
+                message: format!("Failed to downcast service {box_type} to Arc<{service_type}>"),
+            }
+        })?;
+        Ok(service_ptr.clone())
     }
 }
 
 impl Container {
     /// Register type `TProvider` as the provider for type `TService`.
     ///
+    /// The service is registered as a [`Lifetime::Singleton`].
+    ///
     /// # Errors
     /// This method fails if a provider is already registered for `TService`.
-    pub fn register<TProvider, TService: ?Sized>(&mut self) -> Result<(), Error>
+    pub fn register<TProvider, TService>(&mut self) -> Result<(), Error>
     where
-        TProvider: Injectable + Provider<TService>,
-        TService: 'static,
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
     {
-        let service_type = TypeInfo::of::<TService>();
-        let provider_type = TypeInfo::of::<TProvider>();
-        if let Some((prev_provider_type, _)) = self.provide_map.get(&service_type) {
-            return Err(Error::DuplicateRegistration {
-                service: service_type,
-                registered_provider: *prev_provider_type,
-                rejected_provider: provider_type,
-            });
-        }
-        self.register_overwrite::<TProvider, TService>();
+        self.register_with::<TProvider, TService>(Lifetime::Singleton)
+    }
+    /// Same as [`Container::register`], but with an explicit [`Lifetime`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`.
+    pub fn register_with<TProvider, TService>(&mut self, lifetime: Lifetime) -> Result<(), Error>
+    where
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.check_duplicate(
+            ServiceKey::of::<TService>(None),
+            TypeInfo::of::<TProvider>(),
+        )?;
+        self.register_provider::<TProvider, TService>(
+            None,
+            lifetime,
+            Origin::Register,
+            injectable_factory::<TProvider>,
+        );
+        Ok(())
+    }
+    /// Register type `TProvider` as the provider for type `TService` under
+    /// the given name. Named registrations are independent of each other
+    /// and of the unnamed registration, and are resolved with
+    /// [`Container::resolve_named`].
+    ///
+    /// The service is registered as a [`Lifetime::Singleton`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`
+    /// with the same name.
+    pub fn register_named<TProvider, TService>(&mut self, name: &'static str) -> Result<(), Error>
+    where
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.register_named_with::<TProvider, TService>(name, Lifetime::Singleton)
+    }
+    /// Same as [`Container::register_named`], but with an explicit [`Lifetime`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`
+    /// with the same name.
+    pub fn register_named_with<TProvider, TService>(
+        &mut self,
+        name: &'static str,
+        lifetime: Lifetime,
+    ) -> Result<(), Error>
+    where
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.check_duplicate(
+            ServiceKey::of::<TService>(Some(name)),
+            TypeInfo::of::<TProvider>(),
+        )?;
+        self.register_provider::<TProvider, TService>(
+            Some(name),
+            lifetime,
+            Origin::Register,
+            injectable_factory::<TProvider>,
+        );
         Ok(())
     }
     /// Same as [`Container::register`], but overwrites existing registrations.
-    pub fn register_overwrite<TProvider, TService: ?Sized>(&mut self)
+    pub fn register_overwrite<TProvider, TService>(&mut self)
+    where
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.register_overwrite_with::<TProvider, TService>(Lifetime::Singleton);
+    }
+    /// Same as [`Container::register_with`], but overwrites existing registrations.
+    pub fn register_overwrite_with<TProvider, TService>(&mut self, lifetime: Lifetime)
+    where
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.register_provider::<TProvider, TService>(
+            None,
+            lifetime,
+            Origin::RegisterOverwrite,
+            injectable_factory::<TProvider>,
+        );
+    }
+    /// Register an existing instance of type `TService`. The instance is
+    /// returned every time `TService` is resolved.
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`.
+    pub fn register_instance<TService>(&mut self, instance: Arc<TService>) -> Result<(), Error>
+    where
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.register_factory::<TService>(
+            TypeInfo::of::<Arc<TService>>(),
+            Lifetime::Singleton,
+            Origin::RegisterInstance,
+            Arc::new(move |_| Ok(Box::new(Arc::clone(&instance)))),
+        )
+    }
+    /// Register a closure that builds instances of type `TService`, resolving
+    /// its dependencies from the given container.
+    ///
+    /// The service is registered as a [`Lifetime::Singleton`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`.
+    pub fn register_fn<TService, F>(&mut self, factory: F) -> Result<(), Error>
+    where
+        TService: ?Sized + Shareable + 'static,
+        F: Fn(&Self) -> Result<Arc<TService>, Error> + Shareable + 'static,
+    {
+        self.register_fn_with::<TService, F>(Lifetime::Singleton, factory)
+    }
+    /// Same as [`Container::register_fn`], but with an explicit [`Lifetime`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`.
+    pub fn register_fn_with<TService, F>(
+        &mut self,
+        lifetime: Lifetime,
+        factory: F,
+    ) -> Result<(), Error>
+    where
+        TService: ?Sized + Shareable + 'static,
+        F: Fn(&Self) -> Result<Arc<TService>, Error> + Shareable + 'static,
+    {
+        self.register_factory::<TService>(
+            TypeInfo::of::<F>(),
+            lifetime,
+            Origin::RegisterFn,
+            Arc::new(move |container| Ok(Box::new(factory(container)?))),
+        )
+    }
+    // Registrations on a scope diverge from its parent's, so the parent's
+    // singletons may have been built from providers the scope replaced.
+    #[cfg_attr(not(feature = "sync"), allow(clippy::arc_with_non_send_sync))]
+    fn registry_mut(&mut self) -> RegistryMut<'_> {
+        if std::mem::take(&mut self.inherits_registry) {
+            self.registry = Arc::new(Mutex::new(self.registry()));
+            self.singletons = Arc::default();
+            self.root_scoped = Arc::clone(&self.scoped);
+        }
+        RegistryMut(self.registry.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+    fn check_duplicate(&self, key: ServiceKey, rejected_provider: TypeInfo) -> Result<(), Error> {
+        self.conflicting_provider(key, rejected_provider)
+            .map_or(Ok(()), |registered_provider| {
+                Err(Error::DuplicateRegistration {
+                    service: key.service,
+                    name: key.name,
+                    registered_provider,
+                    rejected: vec![Resolution {
+                        service: key.service,
+                        name: key.name,
+                        provider: rejected_provider,
+                    }],
+                })
+            })
+    }
+    fn conflicting_provider(&self, key: ServiceKey, provider: TypeInfo) -> Option<TypeInfo> {
+        // registering a provider as itself replaces its implicit registration
+        let implicit =
+            |prev: &&Registration| prev.origin == Origin::Implicit && prev.provider == provider;
+        let registry = self.registry();
+        let prev = registry.provide_map.get(&key);
+        prev.filter(|prev| !implicit(prev))
+            .map(|prev| prev.provider)
+    }
+    // Register a factory that builds `Arc<TService>` directly, with no
+    // `Provider` implementation in between.
+    fn register_factory<TService>(
+        &mut self,
+        provider_type: TypeInfo,
+        lifetime: Lifetime,
+        origin: Origin,
+        factory: Arc<FactoryFn>,
+    ) -> Result<(), Error>
+    where
+        TService: ?Sized + Shareable + 'static,
+    {
+        let key = ServiceKey::of::<TService>(None);
+        self.check_duplicate(key, provider_type)?;
+
+        let service_type = key.service;
+        self.registry_mut().provider_factories.insert(
+            provider_type,
+            ProviderFactory {
+                build: BuildFn::Sync(factory),
+                dependencies: Vec::new,
+            },
+        );
+        self.registry_mut().provide_map.insert(
+            key,
+            Registration {
+                provider: provider_type,
+                converter: ServiceConverter(Arc::new(move |any| {
+                    let service = any.downcast_ref::<Arc<TService>>().ok_or_else(|| {
+                        let box_type = TypeInfo::of::<DynamicBox>();
+                        Error::Internal {
+                            message: format!(
+                                "Failed to downcast provider {box_type} to Arc<{service_type}>"
+                            ),
+                        }
+                    })?;
+                    Ok(Box::new(Arc::clone(service)))
+                })),
+                lifetime,
+                origin,
+            },
+        );
+        Ok(())
+    }
+    fn register_provider<TProvider, TService>(
+        &mut self,
+        name: Option<&'static str>,
+        lifetime: Lifetime,
+        origin: Origin,
+        factory: fn() -> ProviderFactory,
+    ) where
+        TProvider: Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        let registration =
+            self.prepare_registration::<TProvider, TService>(lifetime, origin, factory);
+        self.registry_mut()
+            .provide_map
+            .insert(ServiceKey::of::<TService>(name), registration);
+    }
+    fn prepare_registration<TProvider, TService>(
+        &mut self,
+        lifetime: Lifetime,
+        origin: Origin,
+        factory: fn() -> ProviderFactory,
+    ) -> Registration
     where
-        TProvider: Injectable + Provider<TService>,
-        TService: 'static,
+        TProvider: Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
     {
         let service_type = TypeInfo::of::<TService>();
         let provider_type = TypeInfo::of::<TProvider>();
 
         // always allow resolving concrete provider types
-        if service_type != provider_type && !self.provider_factories.contains_key(&provider_type) {
-            self.register_overwrite::<TProvider, TProvider>();
+        if service_type != provider_type
+            && !self
+                .registry()
+                .provider_factories
+                .contains_key(&provider_type)
+        {
+            self.register_provider::<TProvider, TProvider>(
+                None,
+                lifetime,
+                Origin::Implicit,
+                factory,
+            );
         }
 
-        self.provider_factories
+        self.registry_mut()
+            .provider_factories
             .entry(provider_type)
-            .or_insert_with(|| {
-                ProviderFactory(Arc::new(|c| {
-                    let instance = TProvider::inject(c)?;
-                    let arc: Arc<TProvider> = Arc::new(instance);
-                    Ok(Box::new(arc))
-                }))
-            });
+            .or_insert_with(factory);
 
-        self.provide_map.insert(
-            service_type,
-            (
-                provider_type,
-                // TODO: Fix funky coverage results for format! macro and downcast_ref method.
-                //       https://github.com/xd009642/tarpaulin/issues/351
-                ServiceConverter(Arc::new(move |any| {
-                    let provider = any
-                        .downcast_ref::<Arc<TProvider>>()
-                        .ok_or({
-                            let box_type = TypeInfo::of::<DynamicBox>();
-                            Error::Internal {
-                                message: format!(
-                                    "Failed to downcast provider {box_type} to Arc<{service_type}>"
-                                ),
-                            }
-                        })?
-                        .clone();
-                    let service: Arc<TService> = provider.provide();
-                    Ok(Box::new(service))
-                })),
-            ),
+        Registration {
+            provider: provider_type,
+            // TODO: Fix funky coverage results for format! macro and downcast_ref method.
+            //       https://github.com/xd009642/tarpaulin/issues/351
+            converter: ServiceConverter(Arc::new(move |any| {
+                let provider = any
+                    .downcast_ref::<Arc<TProvider>>()
+                    .ok_or_else(|| {
+                        let box_type = TypeInfo::of::<DynamicBox>();
+                        Error::Internal {
+                            message: format!(
+                                "Failed to downcast provider {box_type} to Arc<{service_type}>"
+                            ),
+                        }
+                    })?
+                    .clone();
+                let service: Arc<TService> = provider.provide();
+                Ok(Box::new(service))
+            })),
+            lifetime,
+            origin,
+        }
+    }
+    /// Add type `TProvider` to the providers for type `TService`, to be
+    /// resolved together with [`Container::resolve_all`]. Unlike
+    /// [`Container::register`], multiple providers can be registered this way
+    /// for the same service.
+    ///
+    /// The service is registered as a [`Lifetime::Singleton`].
+    pub fn register_many<TProvider, TService>(&mut self)
+    where
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.register_many_with::<TProvider, TService>(Lifetime::Singleton);
+    }
+    /// Same as [`Container::register_many`], but with an explicit [`Lifetime`].
+    pub fn register_many_with<TProvider, TService>(&mut self, lifetime: Lifetime)
+    where
+        TProvider: Injectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        let registration = self.prepare_registration::<TProvider, TService>(
+            lifetime,
+            Origin::Register,
+            injectable_factory::<TProvider>,
         );
+        self.registry_mut()
+            .multi_map
+            .entry(TypeInfo::of::<TService>())
+            .or_default()
+            .push(registration);
     }
     /// Resolve an instance of type `T`.
     ///
+    /// Singleton services are cached after the first resolution, scoped
+    /// services are cached per scope (see [`Container::create_scope`]), and
+    /// transient services are rebuilt from their provider every time.
+    ///
     /// # Errors
     /// This method fails if no provider has been registered for `T` or
     /// any of its transitive dependencies.
-    pub fn resolve<T>(&mut self) -> Result<Arc<T>, Error>
+    pub fn resolve<T>(&self) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.resolve_key(ServiceKey::of::<T>(None))
+    }
+    /// Resolve an instance of type `T` registered under the given name with
+    /// [`Container::register_named`].
+    ///
+    /// # Errors
+    /// This method fails if no provider has been registered for `T` with
+    /// this name, or for any of its transitive dependencies.
+    pub fn resolve_named<T>(&self, name: &'static str) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.resolve_key(ServiceKey::of::<T>(Some(name)))
+    }
+    /// Resolve an instance of type `T` if a provider has been registered for
+    /// it, or `None` otherwise.
+    ///
+    /// # Errors
+    /// Unlike [`Container::resolve`], a missing provider for `T` itself is
+    /// not an error. This method still fails if any of its transitive
+    /// dependencies can't be resolved.
+    pub fn try_resolve<T>(&self) -> Result<Option<Arc<T>>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.try_resolve_key(ServiceKey::of::<T>(None))
+    }
+    /// Same as [`Container::try_resolve`], but for a named registration.
+    ///
+    /// # Errors
+    /// This method fails if any of the transitive dependencies of `T` can't
+    /// be resolved.
+    pub fn try_resolve_named<T>(&self, name: &'static str) -> Result<Option<Arc<T>>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.try_resolve_key(ServiceKey::of::<T>(Some(name)))
+    }
+    fn try_resolve_key<T>(&self, key: ServiceKey) -> Result<Option<Arc<T>>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        if self.is_provided(key) {
+            self.resolve_key(key).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+    /// Resolve instances of type `T` from every provider registered with
+    /// [`Container::register_many`], in registration order.
+    ///
+    /// Returns an empty list if no providers have been registered.
+    ///
+    /// # Errors
+    /// This method fails if any of the providers' transitive dependencies
+    /// can't be resolved.
+    pub fn resolve_all<T>(&self) -> Result<Vec<Arc<T>>, Error>
     where
         T: ?Sized + 'static,
     {
-        let service_type = TypeInfo::of::<T>();
-        let service_ptr = match self.services.get(&service_type) {
-            Some(ptr) => ptr,
-            None => self.init_service(service_type)?,
+        let key = ServiceKey::of::<T>(None);
+        self.multi_registrations(key)
+            .iter()
+            .map(|registration| {
+                let service = self.init_service(key, registration)?;
+                Self::downcast_service(key.service, &service)
+            })
+            .collect()
+    }
+    /// Check that every registered service can be resolved, without
+    /// building any providers.
+    ///
+    /// Dependencies are read from [`Injectable::dependencies`], so providers
+    /// registered with [`Container::register_fn`] or implementing
+    /// [`Injectable`] manually are only checked as far as they declare
+    /// their dependencies.
+    ///
+    /// # Errors
+    /// Returns every [`Error::NoProvider`] and [`Error::DependencyCycle`]
+    /// found in the dependency graph.
+    pub fn validate(&self) -> Result<(), Vec<Error>> {
+        let mut validator = Validator {
+            container: self,
+            stack: Vec::new(),
+            visited: HashSet::new(),
+            errors: Vec::new(),
         };
 
-        let service_ptr = service_ptr.downcast_ref::<Arc<T>>().ok_or_else(|| {
-            let box_type = TypeInfo::of::<DynamicBox>();
-            Error::Internal {
-                message: format!("Failed to downcast service {box_type} to Arc<{service_type}>"),
+        let registry = self.registry();
+        let mut services: Vec<_> = registry.provide_map.iter().collect();
+        services.sort_by_key(|(key, _)| (key.service.name, key.name));
+        for (key, registration) in services {
+            validator.visit(*key, registration);
+        }
+        let mut collections: Vec<_> = registry.multi_map.iter().collect();
+        collections.sort_by_key(|(service, _)| service.name);
+        for (service, registrations) in collections {
+            let key = ServiceKey {
+                service: *service,
+                name: None,
+            };
+            for registration in registrations {
+                validator.visit(key, registration);
             }
-        })?;
-        Ok(service_ptr.clone())
+        }
+
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.errors)
+        }
+    }
+    fn resolve_key<T>(&self, key: ServiceKey) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        let registration = self.registration(key)?;
+        if let Some(cache) = self.cache(registration.lifetime) {
+            if let Some(service_ptr) = cache.services.get(&key) {
+                return Self::downcast_service(key.service, service_ptr);
+            }
+        }
+
+        let service = self.init_service(key, &registration)?;
+        match self.cache(registration.lifetime) {
+            // keep the first instance if another thread won the race
+            Some(mut cache) => {
+                Self::downcast_service(key.service, cache.services.entry(key).or_insert(service))
+            }
+            None => Self::downcast_service(key.service, &service),
+        }
+    }
+}
+
+// Depth-first walk of the dependency graph for Container::validate.
+struct Validator<'a> {
+    container: &'a Container,
+    stack: Vec<Resolution>,
+    visited: HashSet<Resolution>,
+    errors: Vec<Error>,
+}
+
+impl Validator<'_> {
+    fn visit(&mut self, key: ServiceKey, registration: &Registration) {
+        let res = Resolution {
+            service: key.service,
+            name: key.name,
+            provider: registration.provider,
+        };
+        if self.stack.contains(&res) {
+            let mut stack = self.stack.clone();
+            stack.push(res);
+            self.report(Error::DependencyCycle {
+                service: res.service,
+                stack,
+            });
+            return;
+        }
+        if !self.visited.insert(res) {
+            return;
+        }
+
+        let registry = self.container.registry();
+        let dependencies = registry
+            .provider_factories
+            .get(&registration.provider)
+            .map_or_else(Vec::new, |factory| (factory.dependencies)());
+        self.stack.push(res);
+        for dependency in dependencies {
+            let key = ServiceKey {
+                service: dependency.service,
+                name: dependency.name,
+            };
+            if dependency.kind == DependencyKind::Collection {
+                for registration in self.container.multi_registrations(key) {
+                    self.visit(key, &registration);
+                }
+                continue;
+            }
+            match (registry.provide_map.get(&key), dependency.kind) {
+                (Some(registration), DependencyKind::Required | DependencyKind::Optional) => {
+                    self.visit(key, registration);
+                }
+                (None, DependencyKind::Optional) | (Some(_), _) => {}
+                (None, _) => {
+                    let error = self.container.no_provider(key, self.stack.clone());
+                    self.report(error);
+                }
+            }
+        }
+        self.stack.pop();
+    }
+    fn report(&mut self, error: Error) {
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+}
+
+fn injectable_factory<TProvider>() -> ProviderFactory
+where
+    TProvider: Injectable + Shareable,
+{
+    ProviderFactory {
+        build: BuildFn::Sync(Arc::new(|c| {
+            let instance = TProvider::inject(c)?;
+            let arc: Arc<TProvider> = Arc::new(instance);
+            Ok(Box::new(arc))
+        })),
+        dependencies: TProvider::dependencies,
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(not(feature = "sync"), allow(clippy::future_not_send))]
+impl Container {
+    /// Register type `TProvider` as the async provider for type `TService`.
+    ///
+    /// The service is registered as a [`Lifetime::Singleton`], and must be
+    /// resolved with [`Container::resolve_async`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`.
+    pub fn register_async<TProvider, TService>(&mut self) -> Result<(), Error>
+    where
+        TProvider: AsyncInjectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.register_async_with::<TProvider, TService>(Lifetime::Singleton)
+    }
+    /// Same as [`Container::register_async`], but with an explicit [`Lifetime`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`.
+    pub fn register_async_with<TProvider, TService>(
+        &mut self,
+        lifetime: Lifetime,
+    ) -> Result<(), Error>
+    where
+        TProvider: AsyncInjectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.check_duplicate(
+            ServiceKey::of::<TService>(None),
+            TypeInfo::of::<TProvider>(),
+        )?;
+        self.register_provider::<TProvider, TService>(
+            None,
+            lifetime,
+            Origin::Register,
+            async_injectable_factory::<TProvider>,
+        );
+        Ok(())
+    }
+    /// Same as [`Container::register_named`], but for async providers.
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`
+    /// with the same name.
+    pub fn register_named_async<TProvider, TService>(
+        &mut self,
+        name: &'static str,
+    ) -> Result<(), Error>
+    where
+        TProvider: AsyncInjectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.register_named_async_with::<TProvider, TService>(name, Lifetime::Singleton)
+    }
+    /// Same as [`Container::register_named_async`], but with an explicit
+    /// [`Lifetime`].
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for `TService`
+    /// with the same name.
+    pub fn register_named_async_with<TProvider, TService>(
+        &mut self,
+        name: &'static str,
+        lifetime: Lifetime,
+    ) -> Result<(), Error>
+    where
+        TProvider: AsyncInjectable + Provider<TService> + Shareable,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.check_duplicate(
+            ServiceKey::of::<TService>(Some(name)),
+            TypeInfo::of::<TProvider>(),
+        )?;
+        self.register_provider::<TProvider, TService>(
+            Some(name),
+            lifetime,
+            Origin::Register,
+            async_injectable_factory::<TProvider>,
+        );
+        Ok(())
+    }
+    /// Resolve an instance of type `T`, awaiting async providers.
+    ///
+    /// Services are cached the same way as with [`Container::resolve`].
+    /// If multiple tasks resolve the same uninitialized service at once,
+    /// its provider is only built once, and all tasks receive the same instance.
+    ///
+    /// # Errors
+    /// This method fails if no provider has been registered for `T` or
+    /// any of its transitive dependencies.
+    pub async fn resolve_async<T>(&self) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.resolve_key_async(ServiceKey::of::<T>(None)).await
+    }
+    /// Same as [`Container::resolve_named`], but awaits async providers.
+    ///
+    /// # Errors
+    /// This method fails if no provider has been registered for `T` with
+    /// this name, or for any of its transitive dependencies.
+    pub async fn resolve_named_async<T>(&self, name: &'static str) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.resolve_key_async(ServiceKey::of::<T>(Some(name)))
+            .await
+    }
+    /// Same as [`Container::try_resolve`], but awaits async providers.
+    ///
+    /// # Errors
+    /// This method fails if any of the transitive dependencies of `T` can't
+    /// be resolved.
+    pub async fn try_resolve_async<T>(&self) -> Result<Option<Arc<T>>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.try_resolve_key_async(ServiceKey::of::<T>(None)).await
+    }
+    /// Same as [`Container::try_resolve_named`], but awaits async providers.
+    ///
+    /// # Errors
+    /// This method fails if any of the transitive dependencies of `T` can't
+    /// be resolved.
+    pub async fn try_resolve_named_async<T>(
+        &self,
+        name: &'static str,
+    ) -> Result<Option<Arc<T>>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        self.try_resolve_key_async(ServiceKey::of::<T>(Some(name)))
+            .await
+    }
+    async fn try_resolve_key_async<T>(&self, key: ServiceKey) -> Result<Option<Arc<T>>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        if self.is_provided(key) {
+            self.resolve_key_async(key).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+    /// Same as [`Container::resolve_all`], but awaits async providers.
+    ///
+    /// # Errors
+    /// This method fails if any of the providers' transitive dependencies
+    /// can't be resolved.
+    pub async fn resolve_all_async<T>(&self) -> Result<Vec<Arc<T>>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        let key = ServiceKey::of::<T>(None);
+        let mut services = Vec::new();
+        for registration in self.multi_registrations(key) {
+            services.push(self.init_service_async(key, &registration).await?);
+        }
+        services
+            .iter()
+            .map(|service| Self::downcast_service(key.service, service))
+            .collect()
+    }
+    async fn resolve_key_async<T>(&self, key: ServiceKey) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        let registration = self.registration(key)?;
+        if let Some(cache) = self.cache(registration.lifetime) {
+            if let Some(service_ptr) = cache.services.get(&key) {
+                return Self::downcast_service(key.service, service_ptr);
+            }
+        }
+
+        let service = self.init_service_async(key, &registration).await?;
+        match self.cache(registration.lifetime) {
+            Some(mut cache) => {
+                Self::downcast_service(key.service, cache.services.entry(key).or_insert(service))
+            }
+            None => Self::downcast_service(key.service, &service),
+        }
+    }
+    async fn init_service_async(
+        &self,
+        key: ServiceKey,
+        registration: &Registration,
+    ) -> Result<DynamicBox, Error> {
+        let converter = &registration.converter.0;
+        let res = Resolution {
+            service: key.service,
+            name: key.name,
+            provider: registration.provider,
+        };
+        self.check_pending(res)?;
+        // sync providers are built on the sync path, so they're cached once
+        // however they're resolved
+        if !self.is_async_provider(registration.provider) {
+            return self.init_service(key, registration);
+        }
+        let cell = match self.cache(registration.lifetime) {
+            Some(mut cache) => {
+                if let Some(provider) = cache.provider(&registration.provider) {
+                    return converter(provider);
+                }
+                let cell = cache
+                    .async_providers
+                    .entry(registration.provider)
+                    .or_default();
+                Some(Arc::clone(cell))
+            }
+            None => None,
+        };
+        let Some(cell) = cell else {
+            return converter(
+                &self
+                    .build_provider_async(res, registration.lifetime)
+                    .await?,
+            );
+        };
+        let provider = cell
+            .get_or_try_init(|| self.build_provider_async(res, registration.lifetime))
+            .await?;
+        converter(provider)
+    }
+    pub(crate) async fn create_instance_async<T>(
+        &self,
+        name: Option<&'static str>,
+    ) -> Result<Arc<T>, Error>
+    where
+        T: ?Sized + 'static,
+    {
+        let key = ServiceKey::of::<T>(name);
+        let registration = self.registration(key)?;
+        let res = Resolution {
+            service: key.service,
+            name,
+            provider: registration.provider,
+        };
+        let provider = self.build_provider_async(res, Lifetime::Transient).await?;
+        let service = (registration.converter.0)(&provider)?;
+        Self::downcast_service(key.service, &service)
+    }
+    fn is_async_provider(&self, provider: TypeInfo) -> bool {
+        self.registry()
+            .provider_factories
+            .get(&provider)
+            .is_some_and(|factory| matches!(factory.build, BuildFn::Async(_)))
+    }
+    async fn build_provider_async(
+        &self,
+        res: Resolution,
+        lifetime: Lifetime,
+    ) -> Result<DynamicBox, Error> {
+        let (context, factory) = self.resolution_context(res, lifetime)?;
+        match factory.build {
+            BuildFn::Sync(build) => build(&context),
+            BuildFn::Async(build) => build(&context).await,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+fn async_injectable_factory<TProvider>() -> ProviderFactory
+where
+    TProvider: AsyncInjectable + Shareable,
+{
+    ProviderFactory {
+        build: BuildFn::Async(Arc::new(|c| {
+            Box::pin(async move {
+                let instance = TProvider::inject_async(c).await?;
+                let arc: Arc<TProvider> = Arc::new(instance);
+                let provider: DynamicBox = Box::new(arc);
+                Ok(provider)
+            })
+        })),
+        dependencies: TProvider::dependencies,
     }
 }
 
@@ -216,8 +1359,6 @@ impl Container {
 #[allow(clippy::unwrap_used)]
 mod test {
     use crate::*;
-    // TODO: this is a hack. see import_crate in codegen/src/utils.rs
-    use crate as depcon;
     use std::sync::Arc;
 
     #[test]
@@ -240,6 +1381,101 @@ mod test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_register_as() {
+        trait Reader: Send + Sync {}
+        trait Writer: Send + Sync {}
+        #[derive(Injectable)]
+        struct Store;
+        impl Reader for Store {}
+        impl Writer for Store {}
+        provide_trait!(Store, dyn Reader, dyn Writer);
+
+        let mut container = Container::empty();
+        container
+            .register_as::<Store>()
+            .service::<dyn Reader>()
+            .service_named::<dyn Writer>("log")
+            .lifetime(Lifetime::Scoped)
+            .finish()
+            .unwrap();
+        let scope = container.create_scope();
+        for container in [&container, &scope] {
+            let reader = container.resolve::<dyn Reader>().unwrap();
+            let writer = container.resolve_named::<dyn Writer>("log").unwrap();
+            let store = container.resolve::<Store>().unwrap();
+            assert_eq!(
+                Arc::as_ptr(&reader).cast::<()>(),
+                Arc::as_ptr(&store).cast()
+            );
+            assert_eq!(
+                Arc::as_ptr(&writer).cast::<()>(),
+                Arc::as_ptr(&store).cast()
+            );
+        }
+        let store = container.resolve::<Store>().unwrap();
+        let scoped_store = scope.resolve::<Store>().unwrap();
+        assert!(!Arc::ptr_eq(&store, &scoped_store));
+    }
+
+    #[test]
+    fn test_register_as_duplicate() {
+        trait Reader: Send + Sync {}
+        trait Writer: Send + Sync {}
+        #[derive(Injectable)]
+        struct Store;
+        impl Reader for Store {}
+        impl Writer for Store {}
+        provide_trait!(Store, dyn Reader, dyn Writer);
+        #[derive(Injectable)]
+        struct Log;
+        impl Writer for Log {}
+        provide_trait!(Log, dyn Writer);
+
+        let mut container = Container::empty();
+        container.register::<Log, dyn Writer>().unwrap();
+        let actual = container
+            .register_as::<Store>()
+            .service::<dyn Reader>()
+            .service::<dyn Writer>()
+            .finish()
+            .unwrap_err()
+            .to_string();
+        let expected = "Could not register test::Store for dyn test::Writer due to \
+            conflict with existing provider: test::Log. None of its group was \
+            registered: dyn test::Writer, dyn test::Reader"
+            .replace(
+                "test::",
+                "depcon::container::test::test_register_as_duplicate::",
+            );
+        assert_eq!(actual, expected);
+        assert!(!container.is_registered::<dyn Reader>());
+        assert!(!container.is_registered::<Store>());
+    }
+
+    #[test]
+    fn test_register_implicit_provider() {
+        trait Service: Send + Sync {}
+        #[derive(Injectable)]
+        struct Backend;
+        impl Service for Backend {}
+        provide_trait!(Backend, dyn Service);
+
+        let mut container = Container::empty();
+        container.register::<Backend, dyn Service>().unwrap();
+        container
+            .register_with::<Backend, Backend>(Lifetime::Transient)
+            .unwrap();
+        let manifest = container.manifest();
+        let entry = manifest
+            .services
+            .iter()
+            .find(|entry| entry.service == TypeInfo::of::<Backend>())
+            .unwrap();
+        assert_eq!(entry.origin, Origin::Register);
+        assert_eq!(entry.lifetime, Lifetime::Transient);
+    }
+
     #[test]
     fn test_service_converter_failure() {
         #[derive(Injectable, Debug)]
@@ -249,11 +1485,18 @@ mod test {
         let mut container = Container::empty();
         container.register::<Service, Service>().unwrap();
         container
+            .singletons
+            .lock()
+            .unwrap()
             .providers
             .insert(type_info, Box::new(Arc::new(0_u8)));
 
         let actual = container.resolve::<Service>().unwrap_err().to_string();
-        let expected = "Internal error: Failed to downcast provider alloc::boxed::Box<dyn core::any::Any> to Arc<depcon::container::test::test_service_converter_failure::Service>";
+        let expected = format!(
+            "Internal error: Failed to downcast provider {} to \
+            Arc<depcon::container::test::test_service_converter_failure::Service>",
+            TypeInfo::of::<super::DynamicBox>(),
+        );
 
         assert_eq!(actual, expected);
     }
@@ -262,23 +1505,30 @@ mod test {
     fn test_service_downcast_failure() {
         #[derive(Injectable, Debug)]
         struct Service;
-        let type_info = TypeInfo::of::<Service>();
+        let key = super::ServiceKey::of::<Service>(None);
 
         let mut container = Container::empty();
         container.register::<Service, Service>().unwrap();
         container
+            .singletons
+            .lock()
+            .unwrap()
             .services
-            .insert(type_info, Box::new(Arc::new(0_u8)));
+            .insert(key, Box::new(Arc::new(0_u8)));
 
         let actual = container.resolve::<Service>().unwrap_err().to_string();
-        let expected = "Internal error: Failed to downcast service alloc::boxed::Box<dyn core::any::Any> to Arc<depcon::container::test::test_service_downcast_failure::Service>";
+        let expected = format!(
+            "Internal error: Failed to downcast service {} to \
+            Arc<depcon::container::test::test_service_downcast_failure::Service>",
+            TypeInfo::of::<super::DynamicBox>(),
+        );
 
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn test_register_service_resolve_provider() {
-        trait Interface {}
+        trait Interface: Send + Sync {}
 
         #[derive(Injectable, Debug, PartialEq)]
         struct Implementation;
@@ -297,6 +1547,881 @@ mod test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_register_lifetimes() {
+        #[derive(Injectable)]
+        struct SingletonService;
+
+        #[derive(Injectable)]
+        struct TransientService;
+
+        let mut container = Container::empty();
+        container
+            .register_with::<SingletonService, SingletonService>(Lifetime::Singleton)
+            .unwrap();
+        container
+            .register_with::<TransientService, TransientService>(Lifetime::Transient)
+            .unwrap();
+
+        let singleton_a = container.resolve::<SingletonService>().unwrap();
+        let singleton_b = container.resolve::<SingletonService>().unwrap();
+        assert!(Arc::ptr_eq(&singleton_a, &singleton_b));
+
+        let transient_a = container.resolve::<TransientService>().unwrap();
+        let transient_b = container.resolve::<TransientService>().unwrap();
+        assert!(!Arc::ptr_eq(&transient_a, &transient_b));
+    }
+
+    #[test]
+    fn test_create_scope() {
+        #[derive(Injectable)]
+        struct SingletonService;
+
+        #[derive(Injectable)]
+        struct ScopedService;
+
+        let mut container = Container::empty();
+        container
+            .register::<SingletonService, SingletonService>()
+            .unwrap();
+        container
+            .register_with::<ScopedService, ScopedService>(Lifetime::Scoped)
+            .unwrap();
+
+        let first_scope = container.create_scope();
+        let second_scope = container.create_scope();
+
+        let root_singleton = container.resolve::<SingletonService>().unwrap();
+        let scope_singleton = first_scope.resolve::<SingletonService>().unwrap();
+        assert!(Arc::ptr_eq(&root_singleton, &scope_singleton));
+
+        let first = first_scope.resolve::<ScopedService>().unwrap();
+        let first_again = first_scope.resolve::<ScopedService>().unwrap();
+        let second = second_scope.resolve::<ScopedService>().unwrap();
+        assert!(Arc::ptr_eq(&first, &first_again));
+        assert!(!Arc::ptr_eq(&first, &second));
+
+        let weak = Arc::downgrade(&first);
+        drop((first, first_again, first_scope));
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_scope_registration() {
+        trait Database: Send + Sync {
+            fn name(&self) -> &'static str;
+        }
+
+        #[derive(Injectable)]
+        struct RealDatabase;
+        impl Database for RealDatabase {
+            fn name(&self) -> &'static str {
+                "real"
+            }
+        }
+        provide_trait!(RealDatabase, dyn Database);
+
+        #[derive(Injectable)]
+        struct FakeDatabase;
+        impl Database for FakeDatabase {
+            fn name(&self) -> &'static str {
+                "fake"
+            }
+        }
+        provide_trait!(FakeDatabase, dyn Database);
+
+        let mut container = Container::empty();
+        container.register::<RealDatabase, dyn Database>().unwrap();
+        let mut scope = container.create_scope();
+        scope.register_overwrite::<FakeDatabase, dyn Database>();
+
+        assert_eq!(scope.resolve::<dyn Database>().unwrap().name(), "fake");
+        assert_eq!(container.resolve::<dyn Database>().unwrap().name(), "real");
+        assert_eq!(scope.resolve::<dyn Database>().unwrap().name(), "fake");
+    }
+
+    #[test]
+    fn test_singleton_scoped_dependency() {
+        #[derive(Injectable)]
+        struct ScopedService;
+
+        #[derive(Injectable)]
+        struct SingletonService(Arc<ScopedService>);
+
+        let mut container = Container::empty();
+        container
+            .register::<SingletonService, SingletonService>()
+            .unwrap();
+        container
+            .register_with::<ScopedService, ScopedService>(Lifetime::Scoped)
+            .unwrap();
+
+        let scope = container.create_scope();
+        let singleton = scope.resolve::<SingletonService>().unwrap();
+        let scope_scoped = scope.resolve::<ScopedService>().unwrap();
+        let root_scoped = container.resolve::<ScopedService>().unwrap();
+        assert!(!Arc::ptr_eq(&singleton.0, &scope_scoped));
+        assert!(Arc::ptr_eq(&singleton.0, &root_scoped));
+
+        // the singleton doesn't keep the scope's instances alive
+        let weak = Arc::downgrade(&scope_scoped);
+        drop((scope_scoped, scope));
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_register_named() {
+        trait Database: std::fmt::Debug + Send + Sync {}
+
+        #[derive(Injectable, Debug)]
+        struct Primary;
+        impl Database for Primary {}
+        provide_trait!(Primary, dyn Database);
+
+        #[derive(Injectable, Debug)]
+        struct Replica;
+        impl Database for Replica {}
+        provide_trait!(Replica, dyn Database);
+
+        let mut container = Container::empty();
+        container.register::<Primary, dyn Database>().unwrap();
+        container
+            .register_named::<Replica, dyn Database>("replica")
+            .unwrap();
+
+        let primary = container.resolve::<dyn Database>().unwrap();
+        let replica = container.resolve_named::<dyn Database>("replica").unwrap();
+        assert!(!Arc::ptr_eq(&primary, &replica));
+
+        let actual = container
+            .register_named::<Primary, dyn Database>("replica")
+            .unwrap_err()
+            .to_string();
+        let expected = "Could not register \
+            depcon::container::test::test_register_named::Primary for \
+            dyn depcon::container::test::test_register_named::Database named \"replica\" \
+            due to conflict with existing provider: \
+            depcon::container::test::test_register_named::Replica";
+        assert_eq!(actual, expected);
+
+        let actual = container
+            .resolve_named::<dyn Database>("backup")
+            .unwrap_err()
+            .to_string();
+        let expected = "No provider registered for service \
+            dyn test::Database named \"backup\". \
+            Did you mean: dyn test::Database, dyn test::Database named \"replica\"?"
+            .replace("test::", "depcon::container::test::test_register_named::");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_register_many() {
+        trait HealthCheck: Send + Sync {
+            fn name(&self) -> &'static str;
+        }
+
+        #[derive(Injectable)]
+        struct DatabaseCheck;
+        impl HealthCheck for DatabaseCheck {
+            fn name(&self) -> &'static str {
+                "database"
+            }
+        }
+        provide_trait!(DatabaseCheck, dyn HealthCheck);
+
+        #[derive(Injectable)]
+        struct CacheCheck;
+        impl HealthCheck for CacheCheck {
+            fn name(&self) -> &'static str {
+                "cache"
+            }
+        }
+        provide_trait!(CacheCheck, dyn HealthCheck);
+
+        let mut container = Container::empty();
+        assert!(container
+            .resolve_all::<dyn HealthCheck>()
+            .unwrap()
+            .is_empty());
+
+        container.register_many::<DatabaseCheck, dyn HealthCheck>();
+        container.register_many::<CacheCheck, dyn HealthCheck>();
+        container.register_many_with::<CacheCheck, dyn HealthCheck>(Lifetime::Transient);
+
+        let checks = container.resolve_all::<dyn HealthCheck>().unwrap();
+        let names: Vec<_> = checks.iter().map(|check| check.name()).collect();
+        assert_eq!(names, ["database", "cache", "cache"]);
+
+        let checks_again = container.resolve_all::<dyn HealthCheck>().unwrap();
+        assert!(Arc::ptr_eq(&checks[0], &checks_again[0]));
+
+        let actual = container
+            .resolve::<dyn HealthCheck>()
+            .map(drop)
+            .unwrap_err()
+            .to_string();
+        let expected = "No provider registered for service \
+            dyn depcon::container::test::test_register_many::HealthCheck";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_try_resolve() {
+        #[derive(Injectable, Debug, PartialEq)]
+        struct Metrics;
+
+        #[derive(Injectable, Debug, PartialEq)]
+        struct Service(Arc<Metrics>);
+
+        let mut container = Container::empty();
+        assert_eq!(container.try_resolve::<Service>(), Ok(None));
+
+        container.register::<Service, Service>().unwrap();
+        let actual = container.try_resolve::<Service>().unwrap_err().to_string();
+        let expected = "No provider registered for service \
+            depcon::container::test::test_try_resolve::Metrics, required by \
+            depcon::container::test::test_try_resolve::Service \
+            (as depcon::container::test::test_try_resolve::Service)";
+        assert_eq!(actual, expected);
+
+        container.register::<Metrics, Metrics>().unwrap();
+        assert!(container.try_resolve::<Service>().unwrap().is_some());
+        assert_eq!(container.try_resolve_named::<Service>("other"), Ok(None));
+    }
+
+    #[test]
+    fn test_no_provider_stack() {
+        trait Cache: Send + Sync {}
+        trait Repo: Send + Sync {}
+        trait Service: Send + Sync {}
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct RepoImpl(Arc<dyn Cache>);
+        impl Repo for RepoImpl {}
+        provide_trait!(RepoImpl, dyn Repo);
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct ServiceImpl(Arc<dyn Repo>);
+        impl Service for ServiceImpl {}
+        provide_trait!(ServiceImpl, dyn Service);
+
+        let mut container = Container::empty();
+        container.register::<RepoImpl, dyn Repo>().unwrap();
+        container.register::<ServiceImpl, dyn Service>().unwrap();
+
+        let actual = container
+            .resolve::<dyn Service>()
+            .map(drop)
+            .unwrap_err()
+            .to_string();
+        let expected = "No provider registered for service dyn test::Cache, \
+            required by test::RepoImpl (as dyn test::Repo) -> \
+            test::ServiceImpl (as dyn test::Service)"
+            .replace(
+                "test::",
+                "depcon::container::test::test_no_provider_stack::",
+            );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_no_provider_suggestions() {
+        mod postgres {
+            use super::*;
+
+            #[derive(Injectable)]
+            pub struct Database;
+        }
+        trait Database: Send + Sync {}
+
+        #[derive(Injectable)]
+        struct DbImpl;
+        impl Database for DbImpl {}
+        provide_trait!(DbImpl, dyn Database);
+
+        #[derive(Injectable)]
+        struct Metric;
+
+        #[derive(Injectable)]
+        struct Metrics;
+
+        let mut container = Container::empty();
+        container
+            .register::<postgres::Database, postgres::Database>()
+            .unwrap();
+        container
+            .register_named::<DbImpl, dyn Database>("primary")
+            .unwrap();
+        container.register::<Metrics, Metrics>().unwrap();
+
+        let suggestions = |error| match error {
+            Error::NoProvider { suggestions, .. } => suggestions,
+            error => panic!("unexpected error: {error}"),
+        };
+        let resolution = |service, name, provider| Resolution {
+            service,
+            name,
+            provider,
+        };
+        let db_impl = TypeInfo::of::<DbImpl>();
+        let database = TypeInfo::of::<dyn Database>();
+        let postgres = TypeInfo::of::<postgres::Database>();
+
+        let error = container
+            .resolve_named::<DbImpl>("primary")
+            .map(drop)
+            .unwrap_err();
+        assert_eq!(
+            suggestions(error),
+            [
+                resolution(db_impl, None, db_impl),
+                resolution(database, Some("primary"), db_impl),
+            ]
+        );
+
+        let error = container.resolve::<dyn Database>().map(drop).unwrap_err();
+        assert_eq!(
+            suggestions(error),
+            [
+                resolution(postgres, None, postgres),
+                resolution(database, Some("primary"), db_impl),
+            ]
+        );
+
+        let actual = container
+            .resolve::<Metric>()
+            .map(drop)
+            .unwrap_err()
+            .to_string();
+        let expected = "No provider registered for service test::Metric. \
+            Did you mean: test::Metrics?"
+            .replace(
+                "test::",
+                "depcon::container::test::test_no_provider_suggestions::",
+            );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_no_provider_dyn_suggestions() {
+        trait Store: Send + Sync {}
+
+        #[derive(Injectable)]
+        struct SqlStore;
+
+        #[derive(Injectable)]
+        struct MemoryStore;
+        impl Store for MemoryStore {}
+        provide_trait!(MemoryStore, dyn Store);
+
+        let suggestions = |error| match error {
+            Error::NoProvider { suggestions, .. } => suggestions,
+            error => panic!("unexpected error: {error}"),
+        };
+        let sql_store = TypeInfo::of::<SqlStore>();
+        let store = TypeInfo::of::<dyn Store>();
+
+        let mut container = Container::empty();
+        container.register::<SqlStore, SqlStore>().unwrap();
+        let error = container.resolve::<dyn Store>().map(drop).unwrap_err();
+        let services: Vec<_> = suggestions(error).iter().map(|res| res.service).collect();
+        assert_eq!(services, [sql_store]);
+
+        let mut container = Container::empty();
+        container.register::<MemoryStore, dyn Store>().unwrap();
+        let error = container.resolve::<SqlStore>().map(drop).unwrap_err();
+        let services: Vec<_> = suggestions(error).iter().map(|res| res.service).collect();
+        assert_eq!(services, [store]);
+    }
+
+    #[test]
+    fn test_lazy() {
+        #[derive(Injectable)]
+        struct Service;
+
+        let mut container = Container::empty();
+        container
+            .register_with::<Service, Service>(Lifetime::Transient)
+            .unwrap();
+
+        let lazy = container.lazy::<Service>();
+        let first = lazy.get().unwrap();
+        let second = lazy.get().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let lazy = container.lazy::<Service>();
+        drop(container);
+        let actual = lazy.get().map(drop).unwrap_err().to_string();
+        let expected = "Could not resolve \
+            depcon::container::test::test_lazy::Service because its container was dropped";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "sync"), allow(clippy::arc_with_non_send_sync))]
+    fn test_lazy_registered_later() {
+        #[derive(Injectable)]
+        struct Service;
+
+        struct Handle(#[allow(dead_code)] Lazy<Service>);
+
+        let mut container = Container::empty();
+        let lazy = container.lazy::<Service>();
+        container.register::<Service, Service>().unwrap();
+        assert!(lazy.get().is_ok());
+
+        // an instance holding a handle to its own container is freed with it
+        let handle = Arc::new(Handle(container.lazy()));
+        let weak = Arc::downgrade(&handle);
+        container.register_instance(handle).unwrap();
+        drop(container);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_lazy_in_singleton() {
+        #[derive(Injectable)]
+        struct ScopedService;
+
+        #[derive(Injectable)]
+        struct SingletonService(Lazy<ScopedService>);
+
+        let mut container = Container::empty();
+        container
+            .register::<SingletonService, SingletonService>()
+            .unwrap();
+        container
+            .register_with::<ScopedService, ScopedService>(Lifetime::Scoped)
+            .unwrap();
+
+        let scope = container.create_scope();
+        let singleton = scope.resolve::<SingletonService>().unwrap();
+        drop(scope);
+
+        let scoped = singleton.0.get().unwrap();
+        let root_scoped = container.resolve::<ScopedService>().unwrap();
+        assert!(Arc::ptr_eq(&scoped, &root_scoped));
+    }
+
+    #[test]
+    fn test_factory() {
+        #[derive(Injectable)]
+        struct Dependency;
+
+        #[derive(Injectable)]
+        struct Service(Arc<Dependency>);
+
+        let mut container = Container::empty();
+        assert!(matches!(
+            container.factory::<Service>(),
+            Err(Error::NoProvider { .. })
+        ));
+
+        container.register::<Dependency, Dependency>().unwrap();
+        container.register::<Service, Service>().unwrap();
+        let singleton = container.resolve::<Service>().unwrap();
+
+        let factory = container.factory::<Service>().unwrap();
+        let first = factory.create().unwrap();
+        let second = factory.create().unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &singleton));
+        assert!(Arc::ptr_eq(&first.0, &second.0));
+    }
+
+    #[test]
+    fn test_factory_in_singleton() {
+        #[derive(Injectable)]
+        struct Product;
+
+        #[derive(Injectable)]
+        struct SingletonService(Factory<Product>);
+
+        let mut container = Container::empty();
+        container
+            .register::<SingletonService, SingletonService>()
+            .unwrap();
+        container.register::<Product, Product>().unwrap();
+
+        let scope = container.create_scope();
+        let singleton = scope.resolve::<SingletonService>().unwrap();
+        drop(scope);
+        assert!(singleton.0.create().is_ok());
+
+        let factory = container.factory::<Product>().unwrap();
+        drop(container);
+        let actual = factory.create().map(drop).unwrap_err().to_string();
+        let expected = "Could not resolve \
+            depcon::container::test::test_factory_in_singleton::Product because its container was dropped";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_register_instance() {
+        trait Config: Send + Sync {
+            fn port(&self) -> u16;
+        }
+
+        struct StaticConfig;
+        impl Config for StaticConfig {
+            fn port(&self) -> u16 {
+                8080
+            }
+        }
+
+        let instance: Arc<dyn Config> = Arc::new(StaticConfig);
+        let mut container = Container::empty();
+        container.register_instance(Arc::clone(&instance)).unwrap();
+
+        let resolved = container.resolve::<dyn Config>().unwrap();
+        assert!(Arc::ptr_eq(&resolved, &instance));
+        assert_eq!(resolved.port(), 8080);
+
+        let actual = container
+            .register_instance(instance)
+            .unwrap_err()
+            .to_string();
+        let expected = "Could not register \
+            alloc::sync::Arc<dyn depcon::container::test::test_register_instance::Config> for \
+            dyn depcon::container::test::test_register_instance::Config due to \
+            conflict with existing provider: \
+            alloc::sync::Arc<dyn depcon::container::test::test_register_instance::Config>";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_register_fn() {
+        #[derive(Debug, PartialEq)]
+        struct Port(u16);
+
+        #[derive(Debug, PartialEq)]
+        struct Address(String);
+
+        let mut container = Container::empty();
+        container.register_instance(Arc::new(Port(8080))).unwrap();
+        container
+            .register_fn_with(Lifetime::Transient, |c| {
+                let port = c.resolve::<Port>()?;
+                Ok(Arc::new(Address(format!("localhost:{}", port.0))))
+            })
+            .unwrap();
+
+        let first = container.resolve::<Address>().unwrap();
+        let second = container.resolve::<Address>().unwrap();
+        assert_eq!(*first, Address("localhost:8080".to_owned()));
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_register_fn_cycle() {
+        #[derive(Debug)]
+        struct Service;
+
+        let mut container = Container::empty();
+        container
+            .register_fn(Container::resolve::<Service>)
+            .unwrap();
+
+        let error = container.resolve::<Service>().unwrap_err();
+        assert!(matches!(error, Error::DependencyCycle { .. }));
+    }
+
+    #[test]
+    fn test_validate() {
+        #[derive(Injectable)]
+        struct Config;
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Parent(Arc<Child>, Option<Arc<Config>>);
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Child(Lazy<Parent>, Arc<Config>);
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Service(Arc<Config>);
+
+        let mut container = Container::empty();
+        container.register::<Parent, Parent>().unwrap();
+        container.register::<Child, Child>().unwrap();
+        container.register::<Service, Service>().unwrap();
+
+        let no_provider = |provider| Error::NoProvider {
+            service: TypeInfo::of::<Config>(),
+            name: None,
+            stack: vec![Resolution {
+                service: provider,
+                name: None,
+                provider,
+            }],
+            suggestions: Vec::new(),
+        };
+        assert_eq!(
+            container.validate(),
+            Err(vec![
+                no_provider(TypeInfo::of::<Child>()),
+                no_provider(TypeInfo::of::<Service>()),
+            ])
+        );
+
+        container.register::<Config, Config>().unwrap();
+        assert_eq!(container.validate(), Ok(()));
+        assert!(container.singletons.lock().unwrap().providers.is_empty());
+    }
+
+    #[test]
+    fn test_validate_cycle() {
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Parent(Arc<Child>);
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Child(Arc<Parent>);
+
+        let mut container = Container::empty();
+        container.register::<Parent, Parent>().unwrap();
+        container.register::<Child, Child>().unwrap();
+
+        let resolution = |service| Resolution {
+            service,
+            name: None,
+            provider: service,
+        };
+        let child = resolution(TypeInfo::of::<Child>());
+        let parent = resolution(TypeInfo::of::<Parent>());
+        let cycle = Error::DependencyCycle {
+            service: child.service,
+            stack: vec![child, parent, child],
+        };
+        assert_eq!(container.validate(), Err(vec![cycle]));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        trait Database: Send + Sync {}
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Postgres(Arc<Config>);
+        impl Database for Postgres {}
+        provide_trait!(Postgres, dyn Database);
+
+        #[derive(Injectable)]
+        struct Config;
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Pool(Lazy<dyn Database>);
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Repo(Arc<Self>);
+
+        let mut container = Container::empty();
+        container.register::<Postgres, dyn Database>().unwrap();
+        container.register::<Pool, Pool>().unwrap();
+        container.register::<Repo, Repo>().unwrap();
+
+        let actual = container.to_dot();
+        let expected = r#"digraph depcon {
+    node [fontname="monospace"];
+    n0 [label="test::Pool", shape=box];
+    n1 [label="test::Postgres", shape=box];
+    n2 [label="test::Repo", shape=box];
+    n3 [label="dyn test::Database", shape=ellipse];
+    n4 [label="test::Config", shape=ellipse, color=red, fontcolor=red];
+    n1 -> n3 [label="provides"];
+    n0 -> n3 [label="depends on (lazy)", style=dashed];
+    n1 -> n4 [label="depends on"];
+    n2 -> n2 [label="depends on", color=red];
+}
+"#
+        .replace("test::", "depcon::container::test::test_to_dot::");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_manifest() {
+        trait Check: Send + Sync {}
+
+        #[derive(Injectable)]
+        struct Config;
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct DbCheck(Arc<Config>);
+        impl Check for DbCheck {}
+        provide_trait!(DbCheck, dyn Check);
+
+        let mut container = Container::empty();
+        container.register_overwrite::<Config, Config>();
+        container.register_many_with::<DbCheck, dyn Check>(Lifetime::Transient);
+        container.register_instance(Arc::new(8080_u16)).unwrap();
+
+        let entry = |service, provider, multi, lifetime, origin, dependencies| ManifestEntry {
+            service,
+            name: None,
+            multi,
+            provider,
+            lifetime,
+            origin,
+            dependencies,
+        };
+        let config = TypeInfo::of::<Config>();
+        let db_check = TypeInfo::of::<DbCheck>();
+        let dependency = Dependency {
+            field: Some("0"),
+            service: config,
+            name: None,
+            kind: DependencyKind::Required,
+        };
+        let actual = container.manifest();
+        let expected = Manifest {
+            services: vec![
+                entry(
+                    config,
+                    config,
+                    false,
+                    Lifetime::Singleton,
+                    Origin::RegisterOverwrite,
+                    vec![],
+                ),
+                entry(
+                    db_check,
+                    db_check,
+                    false,
+                    Lifetime::Transient,
+                    Origin::Implicit,
+                    vec![dependency],
+                ),
+                entry(
+                    TypeInfo::of::<dyn Check>(),
+                    db_check,
+                    true,
+                    Lifetime::Transient,
+                    Origin::Register,
+                    vec![dependency],
+                ),
+                entry(
+                    TypeInfo::of::<u16>(),
+                    TypeInfo::of::<Arc<u16>>(),
+                    false,
+                    Lifetime::Singleton,
+                    Origin::RegisterInstance,
+                    vec![],
+                ),
+            ],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_manifest_json() {
+        #[derive(Injectable)]
+        struct Config;
+
+        #[derive(Injectable)]
+        #[allow(dead_code)]
+        struct Service(Option<Arc<Config>>);
+
+        let mut container = Container::empty();
+        container
+            .register_named::<Service, Service>("main")
+            .unwrap();
+
+        let actual = container.manifest().to_json().unwrap();
+        let expected = r#"{
+  "services": [
+    {
+      "service": "test::Service",
+      "name": "main",
+      "multi": false,
+      "provider": "test::Service",
+      "lifetime": "Singleton",
+      "origin": "Register",
+      "dependencies": [
+        {
+          "field": "0",
+          "service": "test::Config",
+          "name": null,
+          "kind": "Optional"
+        }
+      ]
+    }
+  ]
+}"#
+        .replace("test::", "depcon::container::test::test_manifest_json::");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_introspection() {
+        trait Greeter: Send + Sync {}
+
+        #[derive(Injectable)]
+        struct English;
+        impl Greeter for English {}
+        provide_trait!(English, dyn Greeter);
+
+        #[derive(Injectable)]
+        struct Counter;
+
+        let mut container = Container::empty();
+        container.register::<English, dyn Greeter>().unwrap();
+        container
+            .register_named_with::<Counter, Counter>("requests", Lifetime::Transient)
+            .unwrap();
+
+        let english = TypeInfo::of::<English>();
+        let counter = TypeInfo::of::<Counter>();
+        let greeter = TypeInfo::of::<dyn Greeter>();
+        let resolution = |service, name, provider| Resolution {
+            service,
+            name,
+            provider,
+        };
+        let mut expected = vec![
+            resolution(english, None, english),
+            resolution(counter, Some("requests"), counter),
+            resolution(greeter, None, english),
+        ];
+        expected.sort_by_key(|resolution| (resolution.service.name, resolution.name));
+        assert_eq!(container.registrations(), expected);
+
+        assert!(container.is_registered::<dyn Greeter>());
+        assert!(container.is_registered::<English>());
+        assert!(!container.is_registered::<Counter>());
+        assert!(container.is_registered_named::<Counter>("requests"));
+
+        assert_eq!(container.provider_of::<dyn Greeter>(), Some(english));
+        assert_eq!(
+            container.provider_of_named::<Counter>("requests"),
+            Some(counter)
+        );
+        assert_eq!(container.provider_of::<Counter>(), None);
+
+        assert!(!container.is_instantiated::<dyn Greeter>());
+        container.resolve::<dyn Greeter>().unwrap();
+        assert!(container.is_instantiated::<dyn Greeter>());
+        assert!(container.is_instantiated::<English>());
+
+        container.resolve_named::<Counter>("requests").unwrap();
+        assert!(!container.is_instantiated_named::<Counter>("requests"));
+    }
+
+    #[test]
+    fn test_type_info_debug() {
+        let actual = format!("{:?}", TypeInfo::of::<u8>());
+        assert!(actual.contains(r#"name: "u8""#), "{actual}");
+    }
+
     #[test]
     fn test_missing_provider_factory() {
         #[derive(Injectable, Debug)]
@@ -304,10 +2429,10 @@ mod test {
 
         let mut container = Container::empty();
         container.register::<Service, Service>().unwrap();
-        container.provider_factories.clear();
+        container.registry_mut().provider_factories.clear();
 
         let error = container.resolve::<Service>().unwrap_err();
-        let actual = format!("{}", error);
+        let actual = format!("{error}");
         let expected = "Internal error: No factory for provider \
             depcon::container::test::test_missing_provider_factory::Service \
             (service: depcon::container::test::test_missing_provider_factory::Service)";
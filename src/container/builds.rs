@@ -0,0 +1,91 @@
+use super::*;
+use std::{
+    sync::Condvar,
+    thread::{self, ThreadId},
+};
+
+/// Providers currently being built, shared by a container and its scopes.
+///
+/// A thread that needs a provider another thread is building waits for it,
+/// unless that thread is already waiting on it in turn. That would be a
+/// dependency cycle split across the two threads' resolution chains.
+#[derive(Default, Debug)]
+pub(super) struct Builds {
+    state: Mutex<BuildState>,
+    finished: Condvar,
+}
+
+#[derive(Default, Debug)]
+struct BuildState {
+    owners: HashMap<BuildKey, ThreadId>, // build -> thread running it
+    waiting: HashMap<ThreadId, BuildKey>, // thread -> build it waits for
+}
+
+// address of the cache the provider is built for, and the provider type
+type BuildKey = (usize, TypeInfo);
+
+/// Claim on a build, released when dropped.
+pub(super) struct BuildClaim<'a> {
+    builds: &'a Builds,
+    key: BuildKey,
+}
+
+impl Builds {
+    /// Wait until no other thread is building `provider` for `cache`, then
+    /// claim the build. Returns `None` if waiting would never finish.
+    pub(super) fn claim(
+        &self,
+        cache: &Arc<Mutex<Cache>>,
+        provider: TypeInfo,
+    ) -> Option<BuildClaim<'_>> {
+        let key = (Arc::as_ptr(cache) as usize, provider);
+        let current = thread::current().id();
+        let mut state = self.lock();
+        while let Some(&owner) = state.owners.get(&key) {
+            if state.waits_on(owner, current) {
+                return None;
+            }
+            state.waiting.insert(current, key);
+            state = self
+                .finished
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+            state.waiting.remove(&current);
+        }
+        state.owners.insert(key, current);
+        drop(state);
+        Some(BuildClaim { builds: self, key })
+    }
+    fn lock(&self) -> MutexGuard<'_, BuildState> {
+        // the state is only mutated by single inserts and removes
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl BuildState {
+    // Whether `thread` is `target`, or waiting on a build `target` runs,
+    // directly or through other waiting threads.
+    fn waits_on(&self, mut thread: ThreadId, target: ThreadId) -> bool {
+        for _ in 0..=self.waiting.len() {
+            if thread == target {
+                return true;
+            }
+            let owner = self
+                .waiting
+                .get(&thread)
+                .and_then(|key| self.owners.get(key));
+            match owner {
+                Some(&owner) => thread = owner,
+                None => return false,
+            }
+        }
+        false
+    }
+}
+
+impl Drop for BuildClaim<'_> {
+    fn drop(&mut self) {
+        self.builds.lock().owners.remove(&self.key);
+        self.builds.finished.notify_all();
+    }
+}
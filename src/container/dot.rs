@@ -0,0 +1,214 @@
+use super::*;
+use crate::error::format_name;
+use std::fmt::Write;
+
+impl Container {
+    /// Render the registered services, providers and their dependencies as a
+    /// [Graphviz](https://graphviz.org) DOT graph.
+    ///
+    /// Services are drawn as ellipses and providers as boxes. A concrete
+    /// provider registered as itself is drawn as a single box. Edges show
+    /// which providers provide which services, and which services each
+    /// provider depends on, as listed by [`Injectable::dependencies`].
+    /// Missing providers and dependency cycles reported by
+    /// [`Container::validate`] are highlighted in red.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph::default();
+
+        let registry = self.registry();
+        let mut registrations: Vec<_> = registry
+            .provide_map
+            .iter()
+            .map(|(key, registration)| (*key, registration))
+            .chain(registry.multi_map.iter().flat_map(|(service, list)| {
+                let key = ServiceKey {
+                    service: *service,
+                    name: None,
+                };
+                list.iter().map(move |registration| (key, registration))
+            }))
+            .collect();
+        registrations.sort_by_key(|(key, registration)| {
+            (key.service.name, key.name, registration.provider.name)
+        });
+
+        let mut providers = Vec::new();
+        for (key, registration) in registrations {
+            let provider = graph.provider(registration.provider);
+            let service = self.dot_service(&mut graph, key);
+            if provider != service {
+                graph.edge(provider, service, "provides", DependencyKind::Required);
+            }
+            if !providers.contains(&registration.provider) {
+                providers.push(registration.provider);
+            }
+        }
+
+        for provider_type in providers {
+            let provider = graph.provider(provider_type);
+            let dependencies = registry
+                .provider_factories
+                .get(&provider_type)
+                .map_or_else(Vec::new, |factory| (factory.dependencies)());
+            for dependency in dependencies {
+                let service = self.dot_service(
+                    &mut graph,
+                    ServiceKey {
+                        service: dependency.service,
+                        name: dependency.name,
+                    },
+                );
+                graph.edge(provider, service, "depends on", dependency.kind);
+            }
+        }
+
+        for error in self.validate().err().unwrap_or_default() {
+            match error {
+                Error::NoProvider { service, name, .. } => {
+                    let node = self.dot_service(&mut graph, ServiceKey { service, name });
+                    graph.nodes[node].highlight = true;
+                }
+                Error::DependencyCycle { stack, .. } => {
+                    for pair in stack.windows(2) {
+                        let (dependent, dependency) = (pair[0], pair[1]);
+                        let service = self.dot_service(
+                            &mut graph,
+                            ServiceKey {
+                                service: dependency.service,
+                                name: dependency.name,
+                            },
+                        );
+                        let from = graph.provider(dependent.provider);
+                        graph.highlight_edges(from, service);
+                        let provider = graph.provider(dependency.provider);
+                        graph.highlight_edges(provider, service);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        graph.render()
+    }
+    // Get the node for a service, which is its provider's node if the
+    // service is a concrete type registered as itself.
+    fn dot_service(&self, graph: &mut DotGraph, key: ServiceKey) -> usize {
+        let self_provided = key.name.is_none()
+            && self
+                .registry()
+                .provide_map
+                .get(&key)
+                .is_some_and(|registration| registration.provider == key.service);
+        if self_provided {
+            graph.provider(key.service)
+        } else {
+            let label = format!("{}{}", key.service, format_name(key.name));
+            graph.node(DotNode::Service(key), label, "ellipse")
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DotNode {
+    Service(ServiceKey),
+    Provider(TypeInfo),
+}
+
+struct NodeEntry {
+    node: DotNode,
+    label: String,
+    shape: &'static str,
+    highlight: bool,
+}
+
+struct EdgeEntry {
+    from: usize,
+    to: usize,
+    label: &'static str,
+    kind: DependencyKind,
+    highlight: bool,
+}
+
+#[derive(Default)]
+struct DotGraph {
+    nodes: Vec<NodeEntry>,
+    edges: Vec<EdgeEntry>,
+}
+
+impl DotGraph {
+    fn node(&mut self, node: DotNode, label: String, shape: &'static str) -> usize {
+        if let Some(index) = self.nodes.iter().position(|entry| entry.node == node) {
+            return index;
+        }
+        self.nodes.push(NodeEntry {
+            node,
+            label,
+            shape,
+            highlight: false,
+        });
+        self.nodes.len() - 1
+    }
+    fn provider(&mut self, provider: TypeInfo) -> usize {
+        self.node(DotNode::Provider(provider), provider.name.to_owned(), "box")
+    }
+    fn edge(&mut self, from: usize, to: usize, label: &'static str, kind: DependencyKind) {
+        let duplicate = self.edges.iter().any(|edge| {
+            edge.from == from && edge.to == to && edge.label == label && edge.kind == kind
+        });
+        if !duplicate {
+            self.edges.push(EdgeEntry {
+                from,
+                to,
+                label,
+                kind,
+                highlight: false,
+            });
+        }
+    }
+    fn highlight_edges(&mut self, from: usize, to: usize) {
+        for edge in &mut self.edges {
+            if edge.from == from && edge.to == to {
+                edge.highlight = true;
+            }
+        }
+    }
+    fn render(&self) -> String {
+        let mut dot = String::from("digraph depcon {\n    node [fontname=\"monospace\"];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = escape(&node.label);
+            let shape = node.shape;
+            let color = if node.highlight {
+                ", color=red, fontcolor=red"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "    n{index} [label=\"{label}\", shape={shape}{color}];"
+            );
+        }
+        for edge in &self.edges {
+            let (from, to) = (edge.from, edge.to);
+            let label = match edge.kind {
+                DependencyKind::Required => edge.label.to_owned(),
+                kind => format!("{} ({})", edge.label, format!("{kind:?}").to_lowercase()),
+            };
+            let style = match edge.kind {
+                DependencyKind::Required | DependencyKind::Collection => "",
+                _ => ", style=dashed",
+            };
+            let color = if edge.highlight { ", color=red" } else { "" };
+            let _ = writeln!(
+                dot,
+                "    n{from} -> n{to} [label=\"{label}\"{style}{color}];"
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+impl Container {
+    /// List every registration in this container, sorted by service, name
+    /// and provider. Providers registered with [`Container::register_many`]
+    /// are listed once per registration.
+    ///
+    /// See [`Container::manifest`] for lifetimes, origins and dependencies.
+    #[must_use]
+    pub fn registrations(&self) -> Vec<Resolution> {
+        self.manifest()
+            .services
+            .into_iter()
+            .map(|entry| Resolution {
+                service: entry.service,
+                name: entry.name,
+                provider: entry.provider,
+            })
+            .collect()
+    }
+    /// Check whether a provider has been registered for type `T`.
+    #[must_use]
+    pub fn is_registered<T>(&self) -> bool
+    where
+        T: ?Sized + 'static,
+    {
+        self.is_provided(ServiceKey::of::<T>(None))
+    }
+    /// Same as [`Container::is_registered`], but for a named registration.
+    #[must_use]
+    pub fn is_registered_named<T>(&self, name: &'static str) -> bool
+    where
+        T: ?Sized + 'static,
+    {
+        self.is_provided(ServiceKey::of::<T>(Some(name)))
+    }
+    /// Check whether the provider for type `T` has already been built and
+    /// cached, so resolving `T` won't build a new instance.
+    ///
+    /// Scoped services are checked in this container's scope. Transient
+    /// services are never instantiated ahead of time.
+    #[must_use]
+    pub fn is_instantiated<T>(&self) -> bool
+    where
+        T: ?Sized + 'static,
+    {
+        self.is_key_instantiated(ServiceKey::of::<T>(None))
+    }
+    /// Same as [`Container::is_instantiated`], but for a named registration.
+    #[must_use]
+    pub fn is_instantiated_named<T>(&self, name: &'static str) -> bool
+    where
+        T: ?Sized + 'static,
+    {
+        self.is_key_instantiated(ServiceKey::of::<T>(Some(name)))
+    }
+    /// Get the provider type registered for type `T`, if any.
+    #[must_use]
+    pub fn provider_of<T>(&self) -> Option<TypeInfo>
+    where
+        T: ?Sized + 'static,
+    {
+        self.provider_of_key(ServiceKey::of::<T>(None))
+    }
+    /// Same as [`Container::provider_of`], but for a named registration.
+    #[must_use]
+    pub fn provider_of_named<T>(&self, name: &'static str) -> Option<TypeInfo>
+    where
+        T: ?Sized + 'static,
+    {
+        self.provider_of_key(ServiceKey::of::<T>(Some(name)))
+    }
+    fn is_key_instantiated(&self, key: ServiceKey) -> bool {
+        let registry = self.registry();
+        let Some(registration) = registry.provide_map.get(&key) else {
+            return false;
+        };
+        self.cache(registration.lifetime)
+            .is_some_and(|cache| cache.provider(&registration.provider).is_some())
+    }
+    fn provider_of_key(&self, key: ServiceKey) -> Option<TypeInfo> {
+        self.registry()
+            .provide_map
+            .get(&key)
+            .map(|registration| registration.provider)
+    }
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+/// How a registration was added to a [`Container`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Origin {
+    /// Registered with [`Container::register`] or one of its variants, such
+    /// as [`Container::register_named`], [`Container::register_many`] or
+    /// [`Container::register_as`].
+    Register,
+    /// Registered with [`Container::register_overwrite`].
+    RegisterOverwrite,
+    /// Registered with [`Container::register_instance`].
+    RegisterInstance,
+    /// Registered with [`Container::register_fn`].
+    RegisterFn,
+    /// Registered by a [`DefaultProviderHook`] while running
+    /// [`Container::auto`].
+    DefaultProviderHook,
+    /// Registered automatically, so that a concrete provider type can be
+    /// resolved as itself.
+    Implicit,
+}
+
+/// Serializable list of every registration in a [`Container`], created with
+/// [`Container::manifest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Manifest {
+    /// Registrations, sorted by service, name and provider
+    pub services: Vec<ManifestEntry>,
+}
+
+/// A single registration in a [`Manifest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ManifestEntry {
+    /// The registered service
+    pub service: TypeInfo,
+    /// The name the service was registered under, if any
+    pub name: Option<&'static str>,
+    /// Whether the service was registered with [`Container::register_many`]
+    pub multi: bool,
+    /// The service's provider type
+    pub provider: TypeInfo,
+    /// The lifetime of the service
+    pub lifetime: Lifetime,
+    /// How the registration was added
+    pub origin: Origin,
+    /// The provider's dependencies, as listed by [`Injectable::dependencies`]
+    pub dependencies: Vec<Dependency>,
+}
+
+impl Container {
+    /// List every registration in this container, with its provider,
+    /// lifetime, origin and dependencies.
+    ///
+    /// With the `serde` feature enabled, the manifest can be serialized,
+    /// e.g. with [`Manifest::to_json`].
+    #[must_use]
+    pub fn manifest(&self) -> Manifest {
+        let registry = self.registry();
+        let single = registry
+            .provide_map
+            .iter()
+            .map(|(key, registration)| (*key, false, registration));
+        let multi = registry
+            .multi_map
+            .iter()
+            .flat_map(|(service, registrations)| {
+                let key = ServiceKey {
+                    service: *service,
+                    name: None,
+                };
+                registrations
+                    .iter()
+                    .map(move |registration| (key, true, registration))
+            });
+        let mut services: Vec<_> = single
+            .chain(multi)
+            .map(|(key, multi, registration)| ManifestEntry {
+                service: key.service,
+                name: key.name,
+                multi,
+                provider: registration.provider,
+                lifetime: registration.lifetime,
+                origin: registration.origin,
+                dependencies: registry
+                    .provider_factories
+                    .get(&registration.provider)
+                    .map_or_else(Vec::new, |factory| (factory.dependencies)()),
+            })
+            .collect();
+        services.sort_by_key(|entry| {
+            (
+                entry.service.name,
+                entry.name,
+                entry.multi,
+                entry.provider.name,
+            )
+        });
+        Manifest { services }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Manifest {
+    /// Serialize the manifest as pretty-printed JSON.
+    ///
+    /// # Errors
+    /// This method fails if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
@@ -0,0 +1,147 @@
+use super::*;
+use std::marker::PhantomData;
+
+/// Builder for registering one provider for several services, created with
+/// [`Container::register_as`].
+///
+/// Nothing is registered until [`RegisterAs::finish`] is called.
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = ""))]
+#[must_use = "services are only registered by calling `finish`"]
+pub struct RegisterAs<'a, TProvider> {
+    container: &'a mut Container,
+    lifetime: Lifetime,
+    services: Vec<GroupService>,
+    #[derivative(Debug = "ignore")]
+    provider: PhantomData<fn() -> TProvider>,
+}
+
+#[derive(Debug)]
+struct GroupService {
+    key: ServiceKey,
+    register: fn(&mut Container, Option<&'static str>, Lifetime),
+}
+
+impl Container {
+    /// Register type `TProvider` as the provider for several services at
+    /// once, e.g. `register_as::<P>().service::<dyn A>().service::<dyn B>()`.
+    ///
+    /// The services are registered with the same [`Lifetime`], so they
+    /// always share one instance of the provider: one per container for
+    /// [`Lifetime::Singleton`], and one per scope for [`Lifetime::Scoped`].
+    /// `TProvider` can be resolved as itself too, sharing the same instance.
+    pub fn register_as<TProvider>(&mut self) -> RegisterAs<'_, TProvider>
+    where
+        TProvider: Injectable + Shareable,
+    {
+        RegisterAs {
+            container: self,
+            lifetime: Lifetime::Singleton,
+            services: Vec::new(),
+            provider: PhantomData,
+        }
+    }
+}
+
+impl<TProvider> RegisterAs<'_, TProvider>
+where
+    TProvider: Injectable + Shareable,
+{
+    /// Add `TService` to the services provided by `TProvider`.
+    pub fn service<TService>(self) -> Self
+    where
+        TProvider: Provider<TService>,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.add_service::<TService>(None)
+    }
+    /// Same as [`RegisterAs::service`], but for a named registration.
+    pub fn service_named<TService>(self, name: &'static str) -> Self
+    where
+        TProvider: Provider<TService>,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.add_service::<TService>(Some(name))
+    }
+    /// Set the [`Lifetime`] of every service. Defaults to
+    /// [`Lifetime::Singleton`].
+    pub const fn lifetime(mut self, lifetime: Lifetime) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+    /// Register the provider for all of the services.
+    ///
+    /// # Errors
+    /// This method fails if a provider is already registered for any of the
+    /// services, or a service was added twice. In that case, none of the
+    /// services are registered.
+    pub fn finish(self) -> Result<(), Error> {
+        let provider = TypeInfo::of::<TProvider>();
+        let conflict = self
+            .services
+            .iter()
+            .enumerate()
+            .find_map(|(index, service)| {
+                let repeated = self.services[..index]
+                    .iter()
+                    .any(|prev| prev.key == service.key);
+                let registered = if repeated {
+                    Some(provider)
+                } else {
+                    self.container.conflicting_provider(service.key, provider)
+                };
+                registered.map(|registered| (service.key, registered))
+            });
+        if let Some((key, registered_provider)) = conflict {
+            // the conflicting registration goes first, then the rest of its group
+            let mut rejected: Vec<_> = self
+                .services
+                .iter()
+                .map(|service| Resolution {
+                    service: service.key.service,
+                    name: service.key.name,
+                    provider,
+                })
+                .collect();
+            rejected.sort_by_key(|res| res.service != key.service || res.name != key.name);
+            return Err(Error::DuplicateRegistration {
+                service: key.service,
+                name: key.name,
+                registered_provider,
+                rejected,
+            });
+        }
+
+        for service in self.services {
+            (service.register)(self.container, service.key.name, self.lifetime);
+        }
+        Ok(())
+    }
+    fn add_service<TService>(mut self, name: Option<&'static str>) -> Self
+    where
+        TProvider: Provider<TService>,
+        TService: ?Sized + Shareable + 'static,
+    {
+        self.services.push(GroupService {
+            key: ServiceKey::of::<TService>(name),
+            register: register_service::<TProvider, TService>,
+        });
+        self
+    }
+}
+
+fn register_service<TProvider, TService>(
+    container: &mut Container,
+    name: Option<&'static str>,
+    lifetime: Lifetime,
+) where
+    TProvider: Injectable + Provider<TService> + Shareable,
+    TService: ?Sized + Shareable + 'static,
+{
+    container.register_provider::<TProvider, TService>(
+        name,
+        lifetime,
+        Origin::Register,
+        injectable_factory::<TProvider>,
+    );
+}
@@ -0,0 +1,72 @@
+use super::*;
+
+impl Container {
+    /// Build a [`Error::NoProvider`] for a service, suggesting registered
+    /// services with a similar type.
+    pub(super) fn no_provider(&self, key: ServiceKey, stack: Vec<Resolution>) -> Error {
+        let mut suggestions: Vec<_> = self
+            .registry()
+            .provide_map
+            .iter()
+            .filter(|(candidate, registration)| {
+                **candidate != key && is_similar(key.service, **candidate, registration.provider)
+            })
+            .map(|(candidate, registration)| Resolution {
+                service: candidate.service,
+                name: candidate.name,
+                provider: registration.provider,
+            })
+            .collect();
+        suggestions.sort_by_key(|res| (res.service.name, res.name));
+        Error::NoProvider {
+            service: key.service,
+            name: key.name,
+            stack,
+            suggestions,
+        }
+    }
+}
+
+fn is_similar(service: TypeInfo, candidate: ServiceKey, provider: TypeInfo) -> bool {
+    // the service is the concrete provider of a registered dyn service
+    if service == provider {
+        return true;
+    }
+    let wanted = short_name(service.name);
+    let found = short_name(candidate.service.name);
+    // `dyn Store` and a concrete `SqlStore` or `StoreImpl`, either way round
+    match (is_dyn(service.name), is_dyn(candidate.service.name)) {
+        (true, false) if found.contains(wanted) => return true,
+        (false, true) if wanted.contains(found) => return true,
+        _ => {}
+    }
+    let max_distance = (wanted.len().max(found.len()) / 4).max(1);
+    edit_distance(wanted, found) <= max_distance
+}
+
+fn is_dyn(name: &str) -> bool {
+    name.starts_with("dyn ")
+}
+
+// `dyn foo::Bar + Send` -> `Bar`, `foo::Baz<u8>` -> `Baz`
+fn short_name(name: &str) -> &str {
+    let name = name.strip_prefix("dyn ").unwrap_or(name);
+    let end = name.find(['<', ' ']).unwrap_or(name.len());
+    name[..end].rsplit("::").next().unwrap_or(name)
+}
+
+// Levenshtein distance, counted in chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,79 @@
+use crate::*;
+use std::sync::Arc;
+
+/// Static description of a dependency of an [`Injectable`] type.
+///
+/// Generated by `#[derive(Injectable)]` for each field, so tools can inspect
+/// the dependency graph without building any providers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Dependency {
+    /// The field the dependency is injected into, if any. Tuple struct
+    /// fields are named by their index.
+    pub field: Option<&'static str>,
+    /// The service that is depended on
+    pub service: TypeInfo,
+    /// The name the service is requested with, if any
+    pub name: Option<&'static str>,
+    /// How the service is requested
+    pub kind: DependencyKind,
+}
+
+/// How a dependency is requested from the container.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DependencyKind {
+    /// Resolved with [`Container::resolve`]. Fails if the service has no
+    /// provider.
+    Required,
+    /// Resolved with [`Container::try_resolve`]. Missing providers are
+    /// allowed.
+    Optional,
+    /// Resolved with [`Container::resolve_all`]. Any number of providers
+    /// are allowed.
+    Collection,
+    /// Created with [`Container::lazy`]. The service isn't resolved until
+    /// it's used, so it can't cause a dependency cycle.
+    Lazy,
+    /// Created with [`Container::factory`]. The service isn't resolved
+    /// until an instance is created, so it can't cause a dependency cycle.
+    Factory,
+}
+
+/// Field types that `#[derive(Injectable)]` can fill, mapped to the service
+/// they depend on.
+#[doc(hidden)]
+pub trait DependencyField {
+    type Service: ?Sized + 'static;
+}
+
+impl<T: ?Sized + 'static> DependencyField for Arc<T> {
+    type Service = T;
+}
+
+impl<T: ?Sized + 'static> DependencyField for Option<Arc<T>> {
+    type Service = T;
+}
+
+impl<T: ?Sized + 'static> DependencyField for Vec<Arc<T>> {
+    type Service = T;
+}
+
+impl<T: ?Sized + 'static> DependencyField for Lazy<T> {
+    type Service = T;
+}
+
+impl<T: ?Sized + 'static> DependencyField for Factory<T> {
+    type Service = T;
+}
+
+/// The service `#[derive(Injectable)]` resolves for an enum's variant
+/// selector, i.e. the `T` in `fn(Arc<T>) -> impl AsRef<str>`.
+#[doc(hidden)]
+pub fn selector_service<F, T, R>(_selector: &F) -> TypeInfo
+where
+    F: Fn(Arc<T>) -> R,
+    T: ?Sized + 'static,
+{
+    TypeInfo::of::<T>()
+}
@@ -1,7 +1,9 @@
 use crate::*;
+use std::sync::Arc;
 
 /// Error type for this crate.
-#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+#[derive(Debug, thiserror::Error, derivative::Derivative, Clone)]
+#[derivative(PartialEq, Eq)]
 pub enum Error {
     /// Could not resolve a service due to a dependency cycle.
     #[error("Could not resolve {service} due to dependency cycle:\n{}", format_type_stack(.stack))]
@@ -12,26 +14,74 @@ pub enum Error {
         stack: Vec<Resolution>,
     },
     /// Could not resolve a service because no provider was registered for it.
-    #[error("No provider registered for service {service}")]
+    #[error(
+        "No provider registered for service {service}{}{}{}",
+        format_name(*.name),
+        format_required_by(.stack),
+        format_suggestions(.suggestions),
+    )]
     NoProvider {
         /// The service that was missing a provider
         service: TypeInfo,
+        /// The name the service was requested with, if any
+        name: Option<&'static str>,
+        /// Stack of dependency resolutions that required the service, empty
+        /// if it was resolved directly
+        stack: Vec<Resolution>,
+        /// Registered services with a similar type, which might have been
+        /// meant instead
+        suggestions: Vec<Resolution>,
     },
     /// Could no register a provider for a service because the service already had
     /// a provider registered.
     #[error(
-        "Could not register {} for {} due to conflict with existing provider: {}",
-        .rejected_provider,
+        "Could not register {} for {}{} due to conflict with existing provider: {}{}",
+        format_rejected_provider(.rejected),
         .service,
+        format_name(*.name),
         .registered_provider,
+        format_group(.rejected),
     )]
     DuplicateRegistration {
         /// The service that was double-registered
         service: TypeInfo,
+        /// The name the service was registered under, if any
+        name: Option<&'static str>,
         /// The provider that was previously registered
         registered_provider: TypeInfo,
-        /// The provider that could not be registered
-        rejected_provider: TypeInfo,
+        /// The registrations that were rejected, starting with the one for
+        /// `service`. With [`crate::Container::register_as`], this is its
+        /// whole group, none of which was registered.
+        rejected: Vec<Resolution>,
+    },
+    /// Could not resolve a service synchronously because its provider must
+    /// be built with [`crate::Container::resolve_async`].
+    #[error("Provider {provider} for service {service} is async and must be resolved with resolve_async")]
+    AsyncProvider {
+        /// The service that was resolved
+        service: TypeInfo,
+        /// The async provider registered for the service
+        provider: TypeInfo,
+    },
+    /// Could not resolve a service through a [`crate::Lazy`] or
+    /// [`crate::Factory`] handle because its container was dropped.
+    #[error("Could not resolve {service} because its container was dropped")]
+    ContainerDropped {
+        /// The service that was resolved
+        service: TypeInfo,
+    },
+    /// Could not resolve a service because its provider failed to construct
+    /// itself, e.g. because a config file couldn't be parsed.
+    ///
+    /// The provider's error is available through
+    /// [`std::error::Error::source`].
+    #[error("Provider {provider} failed to initialize")]
+    Provider {
+        /// The provider that failed
+        provider: TypeInfo,
+        /// The error returned by the provider
+        #[derivative(PartialEq(compare_with = "same_source"))]
+        source: Arc<dyn std::error::Error + Send + Sync>,
     },
     /// An internal invariant was violated.
     #[error("Internal error: {message}")]
@@ -41,10 +91,87 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Create an [`Error::Provider`] for provider type `P` that failed with
+    /// `source`. Useful for implementing [`Injectable::inject`] by hand.
+    pub fn provider<P>(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self
+    where
+        P: ?Sized + 'static,
+    {
+        Self::Provider {
+            provider: TypeInfo::of::<P>(),
+            source: Arc::from(source.into()),
+        }
+    }
+}
+
+// provider errors aren't comparable, so only the same instance is equal
+fn same_source(
+    a: &Arc<dyn std::error::Error + Send + Sync>,
+    b: &Arc<dyn std::error::Error + Send + Sync>,
+) -> bool {
+    Arc::ptr_eq(a, b)
+}
+
 fn format_type_stack(stack: &[Resolution]) -> String {
     stack
         .iter()
-        .map(|r| format!("{} (as {})", r.provider, r.service))
+        .map(format_resolution)
         .collect::<Vec<_>>()
         .join(" ->\n")
 }
+
+fn format_required_by(stack: &[Resolution]) -> String {
+    if stack.is_empty() {
+        return String::new();
+    }
+    let chain = stack
+        .iter()
+        .rev()
+        .map(format_resolution)
+        .collect::<Vec<_>>()
+        .join(" -> ");
+    format!(", required by {chain}")
+}
+
+fn format_suggestions(suggestions: &[Resolution]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let services = suggestions
+        .iter()
+        .map(|r| format!("{}{}", r.service, format_name(r.name)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(". Did you mean: {services}?")
+}
+
+fn format_rejected_provider(rejected: &[Resolution]) -> String {
+    rejected
+        .first()
+        .map(|r| r.provider.to_string())
+        .unwrap_or_default()
+}
+
+fn format_group(group: &[Resolution]) -> String {
+    if group.len() < 2 {
+        return String::new();
+    }
+    let services = group
+        .iter()
+        .map(|r| format!("{}{}", r.service, format_name(r.name)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(". None of its group was registered: {services}")
+}
+
+fn format_resolution(r: &Resolution) -> String {
+    format!("{} (as {}{})", r.provider, r.service, format_name(r.name))
+}
+
+// also used to label services in `Container::to_dot`
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn format_name(name: Option<&str>) -> String {
+    name.map(|name| format!(" named {name:?}"))
+        .unwrap_or_default()
+}
@@ -0,0 +1,67 @@
+use crate::*;
+use std::{fmt, marker::PhantomData, sync::Arc};
+
+/// Handle for creating fresh instances of a service on demand.
+///
+/// Created with [`Container::factory`], or by `#[derive(Injectable)]` for
+/// fields of type `Factory<T>`. Every call to [`Factory::create`] runs the
+/// provider registered for `T` again, regardless of its [`Lifetime`], while
+/// its dependencies are resolved from the container as usual. Like [`Lazy`],
+/// it only holds a [`WeakContainer`].
+pub struct Factory<T: ?Sized> {
+    container: WeakContainer,
+    name: Option<&'static str>,
+    service: PhantomData<fn() -> Arc<T>>,
+}
+
+impl<T: ?Sized + 'static> Factory<T> {
+    pub(crate) const fn new(container: WeakContainer, name: Option<&'static str>) -> Self {
+        Self {
+            container,
+            name,
+            service: PhantomData,
+        }
+    }
+    /// Create a new instance of the service.
+    ///
+    /// # Errors
+    /// This method fails if the container has been dropped, or if the
+    /// service's provider or its dependencies can't be resolved.
+    pub fn create(&self) -> Result<Arc<T>, Error> {
+        self.container
+            .upgrade_for::<T>()?
+            .create_instance(self.name)
+    }
+    /// Same as [`Factory::create`], but awaits async providers.
+    ///
+    /// # Errors
+    /// This method fails if the container has been dropped, or if the
+    /// service's provider or its dependencies can't be resolved.
+    #[cfg(feature = "async")]
+    #[cfg_attr(not(feature = "sync"), allow(clippy::future_not_send))]
+    pub async fn create_async(&self) -> Result<Arc<T>, Error> {
+        self.container
+            .upgrade_for::<T>()?
+            .create_instance_async(self.name)
+            .await
+    }
+}
+
+impl<T: ?Sized> Clone for Factory<T> {
+    fn clone(&self) -> Self {
+        Self {
+            container: self.container.clone(),
+            name: self.name,
+            service: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> fmt::Debug for Factory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Factory")
+            .field("service", &TypeInfo::of::<T>())
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
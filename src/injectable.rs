@@ -8,8 +8,18 @@ pub trait Injectable: Sized + 'static {
     ///
     /// # Errors
     /// Implementations should forward errors from [`Container::resolve`]
-    /// if a dependency can't be resolved.
-    fn inject(container: &mut Container) -> Result<Self, Error>;
+    /// if a dependency can't be resolved, and return [`Error::Provider`]
+    /// (see [`Error::provider`]) if the provider itself fails to initialize.
+    fn inject(container: &Container) -> Result<Self, Error>;
+
+    /// List the dependencies [`Injectable::inject`] resolves, without
+    /// resolving them. Used by [`Container::validate`].
+    ///
+    /// Defaults to an empty list.
+    #[must_use]
+    fn dependencies() -> Vec<Dependency> {
+        Vec::new()
+    }
 }
 
 // TODO: Someday, with specialization :(
@@ -17,7 +27,7 @@ pub trait Injectable: Sized + 'static {
 // where
 //     T: Default + 'static,
 // {
-//     default fn inject(_container: &mut Container) -> Result<Self, Error> {
+//     default fn inject(_container: &Container) -> Result<Self, Error> {
 //         Ok(Self::default())
 //     }
 // }
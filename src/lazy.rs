@@ -0,0 +1,77 @@
+use crate::*;
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+};
+
+/// Handle to a service that is resolved the first time it's used.
+///
+/// Created with [`Container::lazy`], or by `#[derive(Injectable)]` for fields
+/// of type `Lazy<T>`. Since `T` isn't resolved when the handle is injected,
+/// lazy dependencies can be used to break dependency cycles or to defer
+/// building expensive services.
+///
+/// The handle resolves through a [`WeakContainer`], so it doesn't keep the
+/// container's instances alive.
+pub struct Lazy<T: ?Sized> {
+    container: WeakContainer,
+    name: Option<&'static str>,
+    instance: OnceLock<Arc<T>>,
+}
+
+impl<T: ?Sized + 'static> Lazy<T> {
+    pub(crate) const fn new(container: WeakContainer, name: Option<&'static str>) -> Self {
+        Self {
+            container,
+            name,
+            instance: OnceLock::new(),
+        }
+    }
+    /// Resolve the service, or get the instance resolved by a previous call.
+    ///
+    /// # Errors
+    /// This method fails if the container has been dropped, or if the
+    /// service can't be resolved.
+    pub fn get(&self) -> Result<Arc<T>, Error> {
+        if let Some(instance) = self.instance.get() {
+            return Ok(instance.clone());
+        }
+        let container = self.container.upgrade_for::<T>()?;
+        let instance = match self.name {
+            Some(name) => container.resolve_named(name)?,
+            None => container.resolve()?,
+        };
+        Ok(self.instance.get_or_init(|| instance).clone())
+    }
+    /// Same as [`Lazy::get`], but awaits async providers.
+    ///
+    /// # Errors
+    /// This method fails if the container has been dropped, or if the
+    /// service can't be resolved.
+    #[cfg(feature = "async")]
+    #[cfg_attr(not(feature = "sync"), allow(clippy::future_not_send))]
+    pub async fn get_async(&self) -> Result<Arc<T>, Error>
+    where
+        T: Shareable,
+    {
+        if let Some(instance) = self.instance.get() {
+            return Ok(instance.clone());
+        }
+        let container = self.container.upgrade_for::<T>()?;
+        let instance = match self.name {
+            Some(name) => container.resolve_named_async(name).await?,
+            None => container.resolve_async().await?,
+        };
+        Ok(self.instance.get_or_init(|| instance).clone())
+    }
+}
+
+impl<T: ?Sized + 'static> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("service", &TypeInfo::of::<T>())
+            .field("name", &self.name)
+            .field("resolved", &self.instance.get().is_some())
+            .finish_non_exhaustive()
+    }
+}
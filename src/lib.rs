@@ -6,15 +6,26 @@
     clippy::unwrap_used,
     clippy::cargo
 )]
-#![allow(clippy::wildcard_imports)]
+#![allow(clippy::wildcard_imports, clippy::multiple_crate_versions)]
 #![doc = include_str!("../README.md")]
+
+// lets code generated by depcon's own macros refer to `depcon` in this crate
+extern crate self as depcon;
+
+#[cfg(feature = "async")]
+mod async_injectable;
 mod container;
 mod default_provider_hook;
+mod dependency;
 mod error;
+mod factory;
 mod injectable;
+mod lazy;
+mod lifetime;
 mod macros;
 mod provider;
 mod resolution;
+mod shareable;
 mod type_info;
 
 // Used by auto_register! macro
@@ -22,19 +33,31 @@ pub use inventory;
 
 /// Prelude module for guilt-free glob imports
 pub mod prelude {
+    #[cfg(feature = "async")]
+    pub use crate::AsyncInjectable;
     pub use crate::Container;
+    pub use crate::Factory;
     pub use crate::Injectable;
+    pub use crate::Lazy;
+    pub use crate::Lifetime;
 
     #[cfg(feature = "codegen")]
     pub use depcon_codegen::*;
 }
 
-pub use crate::container::Container;
+#[cfg(feature = "async")]
+pub use crate::async_injectable::{AsyncInjectable, BoxFuture};
+pub use crate::container::{Container, Manifest, ManifestEntry, Origin, RegisterAs, WeakContainer};
 pub use crate::default_provider_hook::DefaultProviderHook;
+pub use crate::dependency::{selector_service, Dependency, DependencyField, DependencyKind};
 pub use crate::error::Error;
+pub use crate::factory::Factory;
 pub use crate::injectable::Injectable;
+pub use crate::lazy::Lazy;
+pub use crate::lifetime::Lifetime;
 pub use crate::provider::Provider;
 pub use crate::resolution::Resolution;
+pub use crate::shareable::Shareable;
 pub use crate::type_info::TypeInfo;
 
 #[cfg(feature = "codegen")]
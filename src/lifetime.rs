@@ -0,0 +1,16 @@
+/// Lifetime of a service registration, i.e. how long resolved instances
+/// are reused by a [`crate::Container`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Lifetime {
+    /// A single instance is created on first resolution and shared for the
+    /// life of the container. This is the default for [`crate::Container::register`].
+    Singleton,
+    /// A single instance is created per scope and shared for the life of
+    /// that scope. See [`crate::Container::create_scope`].
+    ///
+    /// A container that isn't a child scope acts as its own scope.
+    Scoped,
+    /// A new provider instance is created on every resolution.
+    Transient,
+}
@@ -1,29 +1,52 @@
-/// Implement `[Provider]<Service>` for a type. Prefer [`crate::provide`] if
-/// codegen is enabled.
+/// Implement `[Provider]<Service>` for a type, for one or more trait object
+/// services. Prefer [`crate::provide`] if codegen is enabled.
 #[macro_export]
 macro_rules! provide_trait {
-    ($provider: ty, $tr: ty) => {
-        impl $crate::Provider<$tr> for $provider {
-            fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<$tr> {
-                self
+    ($provider: ty, $($tr: ty),+ $(,)?) => {
+        $(
+            impl $crate::Provider<$tr> for $provider {
+                fn provide(self: std::sync::Arc<Self>) -> std::sync::Arc<$tr> {
+                    self
+                }
             }
-        }
+        )+
     };
 }
 
-/// Flag a type as the default provider for a service when
+/// Flag a type as the default provider for one or more services when
 /// [`crate::Container::auto`] is used. Prefer [`crate::auto_provide`] if
 /// codegen is enabled.
+///
+/// All of the services share one instance of the provider.
 #[macro_export]
 macro_rules! auto_register {
-    ($provider: ty, $service: ty) => {
+    ($provider: ty, $($service: ty),+ $(,)?) => {
         $crate::inventory::submit! {
             $crate::DefaultProviderHook(|c| {
-                c.register::<$provider, $service>()
+                c.register_as::<$provider>()
+                    $(.service::<$service>())+
+                    .finish()
             })
         }
     };
     ($provider: ty) => {
-        $crate::auto_register!($provider, $provider)
+        $crate::auto_register!($provider, $provider);
+    };
+}
+
+/// Add a type to the providers for one or more services when
+/// [`crate::Container::auto`] is used.
+///
+/// See [`crate::Container::register_many`]. Prefer [`crate::auto_provide`]
+/// with the `multi` parameter if codegen is enabled.
+#[macro_export]
+macro_rules! auto_register_many {
+    ($provider: ty, $($service: ty),+ $(,)?) => {
+        $crate::inventory::submit! {
+            $crate::DefaultProviderHook(|c| {
+                $(c.register_many::<$provider, $service>();)+
+                Ok(())
+            })
+        }
     };
 }
@@ -2,9 +2,12 @@ use crate::*;
 
 /// Debug type representing a resolved service.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Resolution {
     /// The service that was resolved
     pub service: TypeInfo,
+    /// The name the service was registered under, if any
+    pub name: Option<&'static str>,
     /// The service's underlying provider type
     pub provider: TypeInfo,
 }
@@ -0,0 +1,19 @@
+/// Marker trait for types that can be stored in a [`crate::Container`].
+///
+/// With the `sync` feature enabled, this requires `Send + Sync` so containers
+/// can be shared between threads. Otherwise, it's implemented for all types.
+#[cfg(feature = "sync")]
+pub trait Shareable: Send + Sync {}
+
+#[cfg(feature = "sync")]
+impl<T: ?Sized + Send + Sync> Shareable for T {}
+
+/// Marker trait for types that can be stored in a [`crate::Container`].
+///
+/// With the `sync` feature enabled, this requires `Send + Sync` so containers
+/// can be shared between threads. Otherwise, it's implemented for all types.
+#[cfg(not(feature = "sync"))]
+pub trait Shareable {}
+
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> Shareable for T {}
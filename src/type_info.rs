@@ -8,13 +8,11 @@ use std::{
 ///
 /// Basically identical to [`std::any::TypeId`], but with the type's name
 /// for debugging.
-#[derive(derivative::Derivative, Clone, Copy, Eq)]
-#[derivative(Debug)]
+#[derive(Clone, Copy, Debug, Eq)]
 pub struct TypeInfo {
     /// ID of the type
     pub id: TypeId,
     /// Name of the type
-    #[derivative(Debug = "ignore")]
     pub name: &'static str,
 }
 
@@ -46,3 +44,12 @@ impl fmt::Display for TypeInfo {
         self.name.fmt(f)
     }
 }
+
+/// Serialized as the type's name, since [`TypeId`] has no stable
+/// representation.
+#[cfg(feature = "serde")]
+impl serde::Serialize for TypeInfo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name)
+    }
+}
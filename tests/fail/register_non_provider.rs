@@ -3,7 +3,7 @@ use depcon::*;
 #[derive(Injectable)]
 struct NonProvider;
 
-trait DummyService: 'static {}
+trait DummyService: Send + Sync + 'static {}
 
 fn main() {
     let mut container = Container::empty();
@@ -28,8 +28,8 @@ fn test_struct_provider_impl() {
 #[test]
 fn test_resolve_chain() {
     // service traits
-    trait DbService: Debug + 'static {}
-    trait RepoService: Debug + 'static {}
+    trait DbService: Debug + Send + Sync + 'static {}
+    trait RepoService: Debug + Send + Sync + 'static {}
 
     // service impls
     #[derive(Injectable, Debug)]
@@ -58,16 +58,18 @@ fn test_resolve_chain() {
 #[test]
 fn test_dependency_cycle() {
     // service traits
-    trait CycleA: Debug + 'static {}
-    trait CycleB: Debug + 'static {}
+    trait CycleA: Debug + Send + Sync + 'static {}
+    trait CycleB: Debug + Send + Sync + 'static {}
 
     // service impls
     #[derive(Debug, Injectable)]
+    #[allow(dead_code)]
     struct CycleImplA(Arc<dyn CycleB>);
     impl CycleA for CycleImplA {}
     provide_trait!(CycleImplA, dyn CycleA);
 
     #[derive(Debug, Injectable)]
+    #[allow(dead_code)]
     struct CycleImplB(Arc<dyn CycleA>);
     impl CycleB for CycleImplB {}
     provide_trait!(CycleImplB, dyn CycleB);
@@ -86,18 +88,660 @@ fn test_dependency_cycle() {
     assert_eq!(actual, expected);
 }
 
+#[test]
+fn test_resolve_named_fields() {
+    trait Database: Debug + Send + Sync {}
+
+    #[derive(Injectable, Debug)]
+    struct PrimaryDb;
+    impl Database for PrimaryDb {}
+    provide_trait!(PrimaryDb, dyn Database);
+
+    #[derive(Injectable, Debug)]
+    struct ReplicaDb;
+    impl Database for ReplicaDb {}
+    provide_trait!(ReplicaDb, dyn Database);
+
+    #[derive(Injectable, Debug)]
+    #[allow(dead_code)]
+    struct Repository {
+        primary: Arc<dyn Database>,
+        #[inject(name = "replica")]
+        replica: Arc<dyn Database>,
+    }
+
+    let mut c = Container::empty();
+    c.register::<PrimaryDb, dyn Database>().unwrap();
+    c.register_named::<ReplicaDb, dyn Database>("replica")
+        .unwrap();
+    c.register::<Repository, Repository>().unwrap();
+    let result = c.resolve::<Repository>();
+    let actual = format!("{result:?}");
+    let expected = "Ok(Repository { primary: PrimaryDb, replica: ReplicaDb })";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_resolve_optional_fields() {
+    trait Metrics: Debug + Send + Sync {}
+
+    #[derive(Injectable, Debug)]
+    struct StatsdMetrics;
+    impl Metrics for StatsdMetrics {}
+    provide_trait!(StatsdMetrics, dyn Metrics);
+
+    #[derive(Injectable, Debug)]
+    #[allow(dead_code)]
+    struct Handler {
+        metrics: Option<Arc<dyn Metrics>>,
+    }
+
+    let mut c = Container::empty();
+    c.register::<Handler, Handler>().unwrap();
+    let actual = format!("{:?}", c.resolve::<Handler>());
+    let expected = "Ok(Handler { metrics: None })";
+    assert_eq!(actual, expected);
+
+    let mut c = Container::empty();
+    c.register::<StatsdMetrics, dyn Metrics>().unwrap();
+    c.register::<Handler, Handler>().unwrap();
+    let actual = format!("{:?}", c.resolve::<Handler>());
+    let expected = "Ok(Handler { metrics: Some(StatsdMetrics) })";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_optional_dependency_cycle() {
+    #[derive(Injectable, Debug)]
+    #[allow(dead_code)]
+    struct Parent(Option<Arc<Child>>);
+
+    #[derive(Injectable, Debug)]
+    #[allow(dead_code)]
+    struct Child(Arc<Parent>);
+
+    let mut c = Container::empty();
+    c.register::<Parent, Parent>().unwrap();
+    c.register::<Child, Child>().unwrap();
+    let result = c.resolve::<Parent>();
+    assert!(matches!(result, Err(Error::DependencyCycle { .. })));
+}
+
+#[test]
+fn test_lazy_dependency_cycle() {
+    #[derive(Injectable)]
+    struct Parent {
+        child: Lazy<Child>,
+    }
+
+    #[derive(Injectable)]
+    struct Child {
+        parent: Arc<Parent>,
+    }
+
+    let mut c = Container::empty();
+    c.register::<Parent, Parent>().unwrap();
+    c.register::<Child, Child>().unwrap();
+    let parent = c.resolve::<Parent>().unwrap();
+    let child = parent.child.get().unwrap();
+    assert!(Arc::ptr_eq(&parent, &child.parent));
+    assert!(Arc::ptr_eq(&child, &c.resolve::<Child>().unwrap()));
+}
+
+#[test]
+fn test_factory_fields() {
+    #[derive(Injectable)]
+    struct Parser;
+
+    #[derive(Injectable)]
+    struct Importer {
+        parsers: Factory<Parser>,
+    }
+
+    let mut c = Container::empty();
+    c.register_with::<Parser, Parser>(Lifetime::Scoped).unwrap();
+    c.register::<Importer, Importer>().unwrap();
+    let importer = c.resolve::<Importer>().unwrap();
+    let first = importer.parsers.create().unwrap();
+    let second = importer.parsers.create().unwrap();
+    assert!(!Arc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn test_derived_dependencies() {
+    trait Metrics {}
+
+    #[derive(Injectable)]
+    #[allow(dead_code)]
+    struct Handler {
+        #[inject(name = "primary")]
+        db: Arc<String>,
+        metrics: Option<Arc<dyn Metrics>>,
+        plugins: Vec<Arc<dyn Metrics>>,
+        reports: Lazy<u8>,
+        r#type: Factory<u16>,
+    }
+
+    let dependency = |field, service, name, kind| Dependency {
+        field: Some(field),
+        service,
+        name,
+        kind,
+    };
+    let actual = Handler::dependencies();
+    let expected = vec![
+        dependency(
+            "db",
+            TypeInfo::of::<String>(),
+            Some("primary"),
+            DependencyKind::Required,
+        ),
+        dependency(
+            "metrics",
+            TypeInfo::of::<dyn Metrics>(),
+            None,
+            DependencyKind::Optional,
+        ),
+        dependency(
+            "plugins",
+            TypeInfo::of::<dyn Metrics>(),
+            None,
+            DependencyKind::Collection,
+        ),
+        dependency("reports", TypeInfo::of::<u8>(), None, DependencyKind::Lazy),
+        dependency("type", TypeInfo::of::<u16>(), None, DependencyKind::Factory),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_resolve_collection_fields() {
+    trait HealthCheck: Debug + Send + Sync {}
+
+    #[derive(Injectable, Debug)]
+    struct DatabaseCheck;
+    impl HealthCheck for DatabaseCheck {}
+    provide_trait!(DatabaseCheck, dyn HealthCheck);
+
+    #[derive(Injectable, Debug)]
+    struct CacheCheck;
+    impl HealthCheck for CacheCheck {}
+    provide_trait!(CacheCheck, dyn HealthCheck);
+
+    #[derive(Injectable, Debug)]
+    #[allow(dead_code)]
+    struct HealthEndpoint {
+        checks: Vec<Arc<dyn HealthCheck>>,
+    }
+
+    let mut c = Container::empty();
+    c.register_many::<DatabaseCheck, dyn HealthCheck>();
+    c.register_many::<CacheCheck, dyn HealthCheck>();
+    c.register::<HealthEndpoint, HealthEndpoint>().unwrap();
+    let result = c.resolve::<HealthEndpoint>();
+    let actual = format!("{result:?}");
+    let expected = "Ok(HealthEndpoint { checks: [DatabaseCheck, CacheCheck] })";
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_constructor_errors() {
+    use std::{error::Error as _, num::ParseIntError};
+
+    #[derive(Debug)]
+    struct Config {
+        port: &'static str,
+    }
+
+    #[derive(Injectable, Debug)]
+    #[inject(constructor = Server::new)]
+    struct Server {
+        config: Arc<Config>,
+    }
+
+    impl Server {
+        fn new(config: Arc<Config>) -> Result<Self, ParseIntError> {
+            config.port.parse::<u16>()?;
+            Ok(Self { config })
+        }
+    }
+
+    let mut c = Container::empty();
+    c.register_instance(Arc::new(Config { port: "http" }))
+        .unwrap();
+    c.register::<Server, Server>().unwrap();
+    let error = c.resolve::<Server>().unwrap_err();
+    let actual = error.to_string();
+    let expected = format!("Provider {} failed to initialize", TypeInfo::of::<Server>());
+    assert_eq!(actual, expected);
+    let source = error.source().unwrap().to_string();
+    assert_eq!(source, "invalid digit found in string");
+    assert!(matches!(
+        error,
+        Error::Provider { provider, .. } if provider == TypeInfo::of::<Server>()
+    ));
+
+    let mut c = Container::empty();
+    c.register_instance(Arc::new(Config { port: "8080" }))
+        .unwrap();
+    c.register::<Server, Server>().unwrap();
+    let server = c.resolve::<Server>().unwrap();
+    assert_eq!(server.config.port, "8080");
+}
+
+#[test]
+fn test_field_sources() {
+    #[derive(Injectable, Debug)]
+    struct Config;
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn load_name(container: &Container) -> Result<String, Error> {
+        container.resolve::<Config>()?;
+        Ok("primary".to_owned())
+    }
+
+    #[derive(Injectable, Debug)]
+    #[allow(dead_code)]
+    struct Pool {
+        config: Arc<Config>,
+        #[inject(with = load_name)]
+        name: String,
+        #[inject(value = 4 * 2)]
+        size: usize,
+        #[inject(default)]
+        retries: u8,
+        #[inject(skip)]
+        connections: Vec<String>,
+    }
+
+    let mut c = Container::empty();
+    c.register::<Config, Config>().unwrap();
+    c.register::<Pool, Pool>().unwrap();
+    let result = c.resolve::<Pool>();
+    let actual = format!("{result:?}");
+    let expected = "Ok(Pool { config: Config, name: \"primary\", size: 8, retries: 0, \
+        connections: [] })";
+    assert_eq!(actual, expected);
+    assert_eq!(Pool::dependencies().len(), 1);
+}
+
+#[test]
+fn test_enum_select() {
+    #[derive(Debug)]
+    struct Config {
+        storage: &'static str,
+    }
+
+    #[derive(Injectable, Debug)]
+    struct LocalFs;
+
+    #[derive(Injectable, Debug)]
+    struct S3Client;
+
+    #[derive(Injectable, Debug)]
+    #[inject(select = Storage::select)]
+    #[allow(dead_code)]
+    enum Storage {
+        Local(Arc<LocalFs>),
+        S3 { client: Arc<S3Client> },
+    }
+
+    impl Storage {
+        fn select(config: Arc<Config>) -> &'static str {
+            config.storage
+        }
+    }
+
+    let container = |storage| {
+        let mut c = Container::empty();
+        c.register_instance(Arc::new(Config { storage })).unwrap();
+        c.register::<S3Client, S3Client>().unwrap();
+        c.register::<Storage, Storage>().unwrap();
+        c
+    };
+    let result = container("S3").resolve::<Storage>();
+    let actual = format!("{result:?}");
+    let expected = "Ok(S3 { client: S3Client })";
+    assert_eq!(actual, expected);
+
+    let error = container("Local").resolve::<Storage>().unwrap_err();
+    assert!(
+        matches!(error, Error::NoProvider { service, .. } if service == TypeInfo::of::<LocalFs>())
+    );
+
+    let error = container("Ftp").resolve::<Storage>().unwrap_err();
+    let source = std::error::Error::source(&error).unwrap().to_string();
+    assert_eq!(source, "no variant named \"Ftp\"");
+
+    // the selector's config is a required dependency
+    let mut c = Container::empty();
+    c.register::<Storage, Storage>().unwrap();
+    let errors = c.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(
+        matches!(errors[0], Error::NoProvider { service, .. } if service == TypeInfo::of::<Config>())
+    );
+}
+
+#[test]
+fn test_injectable_constructor() {
+    trait Database: Debug + Send + Sync {}
+
+    #[derive(Injectable, Debug)]
+    struct Postgres;
+    impl Database for Postgres {}
+    provide_trait!(Postgres, dyn Database);
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Repository {
+        db: Arc<dyn Database>,
+        replica: Option<Arc<dyn Database>>,
+        cache_size: usize,
+    }
+
+    #[injectable]
+    impl Repository {
+        #[injectable]
+        fn new(
+            db: Arc<dyn Database>,
+            #[inject(name = "replica")] replica: Option<Arc<dyn Database>>,
+        ) -> Self {
+            Self {
+                db,
+                replica,
+                cache_size: 64,
+            }
+        }
+    }
+
+    let mut c = Container::empty();
+    c.register::<Postgres, dyn Database>().unwrap();
+    c.register::<Repository, Repository>().unwrap();
+    let result = c.resolve::<Repository>();
+    let actual = format!("{result:?}");
+    let expected = "Ok(Repository { db: Postgres, replica: None, cache_size: 64 })";
+    assert_eq!(actual, expected);
+    assert_eq!(Repository::dependencies()[1].field, Some("replica"));
+}
+
+#[test]
+fn test_provide_multiple_services() {
+    trait Reader: Send + Sync {}
+    trait Writer: Send + Sync {}
+
+    #[derive(Injectable)]
+    #[provide(dyn Reader, dyn Writer, Arc<Store>)]
+    struct Store;
+    impl Reader for Store {}
+    impl Writer for Store {}
+
+    let mut c = Container::empty();
+    c.register::<Store, dyn Reader>().unwrap();
+    c.register::<Store, dyn Writer>().unwrap();
+    c.register::<Store, Store>().unwrap();
+    let reader = c.resolve::<dyn Reader>().unwrap();
+    let writer = c.resolve::<dyn Writer>().unwrap();
+    let store = c.resolve::<Store>().unwrap();
+    assert_eq!(
+        Arc::as_ptr(&reader).cast::<()>(),
+        Arc::as_ptr(&store).cast()
+    );
+    assert_eq!(
+        Arc::as_ptr(&writer).cast::<()>(),
+        Arc::as_ptr(&store).cast()
+    );
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_resolve_across_threads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    trait Counter: Send + Sync {}
+
+    static COUNTERS_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+    struct CounterImpl;
+    impl Counter for CounterImpl {}
+    provide_trait!(CounterImpl, dyn Counter);
+
+    impl Injectable for CounterImpl {
+        fn inject(_container: &Container) -> Result<Self, Error> {
+            // give the other threads time to race for the same provider
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            COUNTERS_BUILT.fetch_add(1, Ordering::SeqCst);
+            Ok(Self)
+        }
+    }
+
+    let mut container = Container::empty();
+    container.register::<CounterImpl, dyn Counter>().unwrap();
+    let container = Arc::new(container);
+
+    let mut handles = Vec::new();
+    for _ in 0..8 {
+        let container = Arc::clone(&container);
+        handles.push(std::thread::spawn(move || {
+            container.resolve::<dyn Counter>().unwrap()
+        }));
+    }
+    let services = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(services.windows(2).all(|w| Arc::ptr_eq(&w[0], &w[1])));
+    assert_eq!(COUNTERS_BUILT.load(Ordering::SeqCst), 1);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_dependency_cycle_across_threads() {
+    use std::{sync::mpsc, time::Duration};
+
+    struct CycleA;
+    struct CycleB;
+
+    // each constructor waits, so both threads start building before either
+    // resolves the other's service
+    impl Injectable for CycleA {
+        fn inject(container: &Container) -> Result<Self, Error> {
+            std::thread::sleep(Duration::from_millis(50));
+            container.resolve::<CycleB>()?;
+            Ok(Self)
+        }
+    }
+    impl Injectable for CycleB {
+        fn inject(container: &Container) -> Result<Self, Error> {
+            std::thread::sleep(Duration::from_millis(50));
+            container.resolve::<CycleA>()?;
+            Ok(Self)
+        }
+    }
+
+    let mut container = Container::empty();
+    container.register::<CycleA, CycleA>().unwrap();
+    container.register::<CycleB, CycleB>().unwrap();
+    let container = Arc::new(container);
+
+    let (sender, receiver) = mpsc::channel();
+    for first in [true, false] {
+        let container = Arc::clone(&container);
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            let result = if first {
+                container.resolve::<CycleA>().map(drop)
+            } else {
+                container.resolve::<CycleB>().map(drop)
+            };
+            sender.send(result)
+        });
+    }
+    for _ in 0..2 {
+        let result = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(result, Err(Error::DependencyCycle { .. })));
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_providers {
+    use depcon::*;
+    use std::{
+        fmt::Debug,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    trait Pool: Debug + Send + Sync {}
+    trait Repo: Debug + Send + Sync {}
+
+    static POOLS_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug)]
+    struct PoolImpl;
+    impl Pool for PoolImpl {}
+    provide_trait!(PoolImpl, dyn Pool);
+
+    impl AsyncInjectable for PoolImpl {
+        fn inject_async(_container: &Container) -> BoxFuture<'_, Result<Self, Error>> {
+            Box::pin(async {
+                tokio::task::yield_now().await;
+                POOLS_BUILT.fetch_add(1, Ordering::SeqCst);
+                Ok(Self)
+            })
+        }
+    }
+
+    #[derive(Debug, AsyncInjectable)]
+    #[allow(dead_code)]
+    struct RepoImpl {
+        pool: Arc<dyn Pool>,
+    }
+    impl Repo for RepoImpl {}
+    provide_trait!(RepoImpl, dyn Repo);
+
+    #[tokio::test]
+    async fn test_resolve_async() {
+        let mut container = Container::empty();
+        container.register_async::<PoolImpl, dyn Pool>().unwrap();
+        container.register_async::<RepoImpl, dyn Repo>().unwrap();
+
+        let sync_result = container.resolve::<dyn Repo>().unwrap_err().to_string();
+        let expected = "Provider integration::async_providers::RepoImpl for service \
+            dyn integration::async_providers::Repo is async and must be resolved \
+            with resolve_async";
+        assert_eq!(sync_result, expected);
+
+        let (a, b) = tokio::join!(
+            container.resolve_async::<dyn Repo>(),
+            container.resolve_async::<dyn Repo>(),
+        );
+        assert!(Arc::ptr_eq(&a.unwrap(), &b.unwrap()));
+        assert_eq!(POOLS_BUILT.load(Ordering::SeqCst), 1);
+
+        // cached async services can be resolved synchronously
+        assert!(container.resolve::<dyn Pool>().is_ok());
+    }
+
+    #[cfg(feature = "sync")]
+    #[tokio::test]
+    async fn test_resolve_sync_and_async() {
+        use std::time::Duration;
+
+        static CONFIGS_BUILT: AtomicUsize = AtomicUsize::new(0);
+
+        struct Config;
+        impl Injectable for Config {
+            fn inject(_container: &Container) -> Result<Self, Error> {
+                std::thread::sleep(Duration::from_millis(50));
+                CONFIGS_BUILT.fetch_add(1, Ordering::SeqCst);
+                Ok(Self)
+            }
+        }
+
+        let mut container = Container::empty();
+        container.register::<Config, Config>().unwrap();
+        let container = Arc::new(container);
+
+        let sync_container = Arc::clone(&container);
+        let sync = std::thread::spawn(move || sync_container.resolve::<Config>().unwrap());
+        let from_async = container.resolve_async::<Config>().await.unwrap();
+        let from_sync = sync.join().unwrap();
+        assert!(Arc::ptr_eq(&from_async, &from_sync));
+        assert_eq!(CONFIGS_BUILT.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_named_async() {
+        #[derive(Debug, AsyncInjectable)]
+        struct ReplicaPool;
+        impl Pool for ReplicaPool {}
+        provide_trait!(ReplicaPool, dyn Pool);
+
+        let mut container = Container::empty();
+        container
+            .register_named_async_with::<ReplicaPool, dyn Pool>("replica", Lifetime::Transient)
+            .unwrap();
+
+        let first = container.resolve_named_async::<dyn Pool>("replica");
+        let second = container.resolve_named_async::<dyn Pool>("replica");
+        let (first, second) = tokio::join!(first, second);
+        assert!(!Arc::ptr_eq(&first.unwrap(), &second.unwrap()));
+        assert!(container.resolve_async::<dyn Pool>().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_async_cycle() {
+        trait CycleA: Debug + Send + Sync {}
+        trait CycleB: Debug + Send + Sync {}
+
+        #[derive(Debug, AsyncInjectable)]
+        #[allow(dead_code)]
+        struct CycleImplA(Arc<dyn CycleB>);
+        impl CycleA for CycleImplA {}
+        provide_trait!(CycleImplA, dyn CycleA);
+
+        #[derive(Debug, AsyncInjectable)]
+        #[allow(dead_code)]
+        struct CycleImplB(Arc<dyn CycleA>);
+        impl CycleB for CycleImplB {}
+        provide_trait!(CycleImplB, dyn CycleB);
+
+        let mut container = Container::empty();
+        container
+            .register_async::<CycleImplA, dyn CycleA>()
+            .unwrap();
+        container
+            .register_async::<CycleImplB, dyn CycleB>()
+            .unwrap();
+
+        let error = container.resolve_async::<dyn CycleA>().await.unwrap_err();
+        let Error::DependencyCycle { service, stack } = error else {
+            panic!("expected a dependency cycle, got {error}");
+        };
+        assert_eq!(service, TypeInfo::of::<dyn CycleA>());
+        let providers: Vec<_> = stack.iter().map(|res| res.provider).collect();
+        let a = TypeInfo::of::<CycleImplA>();
+        let b = TypeInfo::of::<CycleImplB>();
+        assert_eq!(providers, [a, b, a]);
+    }
+}
+
 mod hook {
     use depcon::*;
     use std::fmt::Debug;
 
-    trait IDb: Debug {}
+    trait IDb: Debug + Send + Sync {}
     #[derive(Debug, Injectable)]
     struct Db;
     impl IDb for Db {}
     provide_trait!(Db, dyn IDb);
     auto_register!(Db, dyn IDb);
 
-    trait IRepo: Debug {}
+    trait IRepo: Debug + Send + Sync {}
     #[derive(Debug, Injectable)]
     struct Repo;
     impl IRepo for Repo {}
@@ -106,13 +750,29 @@ mod hook {
 
     #[test]
     fn test_auto_register() {
-        let mut container = Container::auto().unwrap();
+        let container = Container::auto().unwrap();
         let result = container.resolve::<dyn IRepo>();
 
         let actual = format!("{:?}", result);
         let expected = "Ok(Repo)".to_string();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_auto_register_origin() {
+        let container = Container::auto().unwrap();
+        let origin = |service| {
+            container
+                .manifest()
+                .services
+                .into_iter()
+                .find(|entry| entry.service == service)
+                .map(|entry| entry.origin)
+        };
+        let expected = Some(Origin::DefaultProviderHook);
+        assert_eq!(origin(TypeInfo::of::<dyn IDb>()), expected);
+        assert_eq!(origin(TypeInfo::of::<Db>()), Some(Origin::Implicit));
+    }
 }
 
 #[test]